@@ -0,0 +1,166 @@
+use std::collections::{BTreeMap, HashSet};
+
+use chrono::{NaiveDateTime, TimeDelta};
+
+use crate::{
+    parser::{
+        activity::{Activity, ActivityEvent},
+        namespace::Namespace,
+    },
+    TimestampRange,
+};
+
+/// The default length of the windows in which operations are counted, in seconds
+pub const WINDOW_SECONDS: u64 = 60;
+/// The default minimum number of operations in a window to flag it
+pub const MIN_EVENTS: usize = 100;
+/// The factor by which the operations in a window must exceed the median of all windows
+pub const BASELINE_FACTOR: usize = 10;
+
+/// The kind of a suspicious mass operation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AnomalyKind {
+    /// Many files unlinked
+    MassDeletion,
+    /// Many files truncated to 0 and written again
+    MassRewrite,
+    /// Many files renamed to the given extension
+    MassRename(String),
+}
+
+/// A window with an unusually high number of suspicious operations
+#[derive(Debug)]
+pub struct Anomaly {
+    /// The kind of the operations
+    pub kind: AnomalyKind,
+    /// The start of the window
+    pub start: NaiveDateTime,
+    /// The end of the window
+    pub end: NaiveDateTime,
+    /// The number of distinct inodes affected in the window
+    pub count: usize,
+    /// The deepest directory containing all affected inodes with a known parent
+    pub subtree: String,
+    /// The affected inodes, in the order of their first operation in the window
+    pub inodes: Vec<u64>,
+}
+
+/// Detection of windows with an unusually high number of unlinks, rewrites or renames to a
+/// common extension. Operations are counted once per inode and window, so a single file
+/// rewritten over and over is not flagged. A window is flagged if it has at least `min_events`
+/// affected inodes and `BASELINE_FACTOR` times the median number of affected inodes per window.
+#[derive(Debug)]
+pub struct Anomalies {
+    /// The flagged windows, ordered by start time
+    pub anomalies: Vec<Anomaly>,
+}
+
+impl Anomalies {
+    /// Detect the anomalies in windows of `window` seconds over the analyzed range. A window of 0
+    /// seconds is treated as 1 second.
+    pub fn new(
+        activity: &Activity,
+        namespace: &Namespace,
+        timeline: &TimestampRange,
+        window: u64,
+        min_events: usize,
+    ) -> Self {
+        let window = i64::try_from(window.max(1)).unwrap_or(i64::MAX);
+        let detector = Detector {
+            namespace,
+            start: timeline.start,
+            window,
+            windows: ((timeline.end - timeline.start).num_seconds() / window + 1) as usize,
+            min_events,
+        };
+        let mut anomalies = detector.detect(AnomalyKind::MassDeletion, activity.unlinks.iter());
+        anomalies.extend(detector.detect(AnomalyKind::MassRewrite, activity.rewrites.iter()));
+        let mut by_extension: BTreeMap<&str, Vec<&ActivityEvent>> = BTreeMap::new();
+        for change in activity.extension_changes.iter() {
+            by_extension
+                .entry(change.extension.as_str())
+                .or_default()
+                .push(&change.event);
+        }
+        for (extension, events) in by_extension {
+            anomalies.extend(detector.detect(
+                AnomalyKind::MassRename(extension.to_string()),
+                events.into_iter(),
+            ));
+        }
+        anomalies.sort_by_key(|a| a.start);
+        Self { anomalies }
+    }
+}
+
+/// The settings shared by the detection of all kinds of anomalies
+struct Detector<'a> {
+    namespace: &'a Namespace,
+    start: NaiveDateTime,
+    window: i64,
+    windows: usize,
+    min_events: usize,
+}
+
+impl Detector<'_> {
+    /// Count the distinct inodes of the events per window and flag the windows exceeding the
+    /// thresholds
+    fn detect<'e>(
+        &self,
+        kind: AnomalyKind,
+        events: impl Iterator<Item = &'e ActivityEvent>,
+    ) -> Vec<Anomaly> {
+        let mut windows: BTreeMap<i64, Vec<&ActivityEvent>> = BTreeMap::new();
+        for event in events {
+            let index = (event.timestamp - self.start).num_seconds().max(0) / self.window;
+            windows.entry(index).or_default().push(event);
+        }
+        for events in windows.values_mut() {
+            let mut seen = HashSet::new();
+            events.retain(|e| seen.insert((e.inode, e.generation)));
+        }
+        let mut counts: Vec<usize> = windows.values().map(|w| w.len()).collect();
+        counts.resize(self.windows.max(counts.len()).max(1), 0);
+        counts.sort();
+        let median = counts[counts.len().saturating_sub(1) / 2];
+        let threshold = self.min_events.max(median * BASELINE_FACTOR).max(1);
+        windows
+            .into_iter()
+            .filter(|(_, events)| events.len() >= threshold)
+            .map(|(index, events)| {
+                let start = self.start + TimeDelta::seconds(index * self.window);
+                Anomaly {
+                    kind: kind.clone(),
+                    start,
+                    end: start + TimeDelta::seconds(self.window),
+                    count: events.len(),
+                    subtree: self.subtree(&events),
+                    inodes: events.iter().map(|e| e.inode).collect(),
+                }
+            })
+            .collect()
+    }
+
+    /// The deepest common directory of the parents of the events
+    fn subtree(&self, events: &[&ActivityEvent]) -> String {
+        let mut common: Option<Vec<String>> = None;
+        for parent in events.iter().filter_map(|e| e.parent) {
+            let path = self.namespace.path(parent);
+            let components: Vec<&str> = path.split('/').filter(|c| !c.is_empty()).collect();
+            common = Some(match common {
+                None => components.iter().map(|c| c.to_string()).collect(),
+                Some(common) => common
+                    .into_iter()
+                    .zip(components)
+                    .take_while(|(a, b)| a == b)
+                    .map(|(a, _)| a)
+                    .collect(),
+            });
+        }
+        match common {
+            None => "?".to_string(),
+            Some(common) if common.first().is_some_and(|c| c.starts_with('<')) => common.join("/"),
+            Some(common) => format!("/{}", common.join("/")),
+        }
+    }
+}
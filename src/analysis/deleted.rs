@@ -0,0 +1,43 @@
+use crate::parser::trash::{Trash, TrashEntry};
+
+/// The files still in trash at the end of the analyzed range, i.e. the files that can still be
+/// restored, optionally filtered by path prefix and owner
+#[derive(Debug)]
+pub struct DeletedFiles<'a> {
+    /// The matching files, in the order they were unlinked
+    pub files: Vec<&'a TrashEntry>,
+    /// The amount of data of the matching files with a known size
+    pub bytes: u64,
+    /// The number of matching files with an unknown size
+    pub unknown_size: usize,
+    /// The number of matching entries not known to be files, see `TrashEntry::known_file`
+    pub unknown_type: usize,
+}
+
+impl<'a> DeletedFiles<'a> {
+    /// Collect the files still in trash whose path is below `prefix` and whose owner is `uid`, if
+    /// given. Files with an unknown owner never match an owner filter.
+    pub fn new(trash: &'a Trash, prefix: Option<&str>, uid: Option<u32>) -> Self {
+        let files: Vec<&TrashEntry> = trash
+            .still_in_trash()
+            .filter(|e| prefix.is_none_or(|prefix| has_prefix(&e.path, prefix)))
+            .filter(|e| uid.is_none_or(|uid| e.uid == Some(uid)))
+            .collect();
+        Self {
+            bytes: files.iter().filter_map(|e| e.size).sum(),
+            unknown_size: files.iter().filter(|e| e.size.is_none()).count(),
+            unknown_type: files.iter().filter(|e| !e.known_file).count(),
+            files,
+        }
+    }
+}
+
+/// Whether a path is the prefix path itself or below it. The prefix matches whole path
+/// components only, so `/data` matches `/data/x` but not `/database`.
+pub fn has_prefix(path: &str, prefix: &str) -> bool {
+    let prefix = prefix.trim_end_matches('/');
+    match path.strip_prefix(prefix) {
+        Some(rest) => rest.is_empty() || rest.starts_with('/'),
+        None => false,
+    }
+}
@@ -0,0 +1,64 @@
+use std::collections::HashMap;
+
+use crate::parser::inodes::Inode;
+
+/// Statistics of the files with a single name extension
+#[derive(Debug, Default)]
+pub struct ExtensionStats {
+    /// The name extension, or an empty string for names without one
+    pub extension: String,
+    /// The number of files created
+    pub created: u64,
+    /// The number of files deleted
+    pub deleted: u64,
+    /// The amount of data written to the files
+    pub written: u64,
+    /// The sum of the last known sizes of the files
+    pub final_size: u64,
+}
+
+impl ExtensionStats {
+    /// The average last known size of the files
+    pub fn average_size(&self) -> u64 {
+        self.final_size.checked_div(self.created).unwrap_or(0)
+    }
+}
+
+/// Statistics of the files created in the analyzed range, grouped by the extension of the name
+/// they were created with
+#[derive(Debug)]
+pub struct Extensions {
+    /// Statistics per extension, sorted by the number of created files in descending order
+    pub extensions: Vec<ExtensionStats>,
+}
+
+impl Extensions {
+    /// Create the extension statistics from the given inodes. Only files created in the
+    /// analyzed range have a known name, so all other inodes are ignored.
+    pub fn new(inodes: &[Inode]) -> Self {
+        let mut extensions: HashMap<&str, ExtensionStats> = HashMap::new();
+        for inode in inodes
+            .iter()
+            .filter(|i| i.created.is_some() && i.node_type == Some('f'))
+        {
+            let extension = inode.extension().unwrap_or("");
+            let stats = extensions.entry(extension).or_insert(ExtensionStats {
+                extension: extension.to_string(),
+                ..Default::default()
+            });
+            stats.created += 1;
+            if inode.deleted.is_some() {
+                stats.deleted += 1;
+            }
+            stats.written += inode.written;
+            stats.final_size += inode.last_known_length;
+        }
+        let mut extensions: Vec<ExtensionStats> = extensions.into_values().collect();
+        extensions.sort_by(|a, b| {
+            b.created
+                .cmp(&a.created)
+                .then(a.extension.cmp(&b.extension))
+        });
+        Self { extensions }
+    }
+}
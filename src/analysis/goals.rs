@@ -0,0 +1,56 @@
+use crate::parser::goals::Goals;
+
+/// The number of copies kept for a goal.
+///
+/// This assumes the goals 1 to 9 are the default goals, which keep as many copies as their number.
+/// The changelog only holds goal ids, so goals redefined in the master's goal configuration are
+/// not detected. Other goals (e.g. erasure coding or named goals) are not simple copy goals and
+/// return `None`, so changes involving them are not estimated.
+pub fn copies(goal: u32) -> Option<u32> {
+    (1..=9).contains(&goal).then_some(goal)
+}
+
+/// Estimate of the replication traffic implied by the goal changes, computed as the file size
+/// times the change in copy count
+#[derive(Debug, Default)]
+pub struct GoalImpact {
+    /// The estimated amount of data to replicate because of increased copy counts
+    pub replicated_bytes: u64,
+    /// The estimated amount of data to delete because of decreased copy counts
+    pub removed_bytes: u64,
+    /// The number of goal changes on files included in the estimate
+    pub estimated: u64,
+    /// The number of goal changes that could not be estimated, because the size or previous
+    /// goal is unknown
+    pub unknown: u64,
+    /// The number of goal changes that were not estimated, because the previous or new goal is
+    /// not a simple copy goal
+    pub not_estimated: u64,
+}
+
+impl GoalImpact {
+    /// Estimate the impact of the goal changes. If `assumed_goal` is set, it is used as the
+    /// previous goal of inodes whose goal was not set before in the analyzed range.
+    pub fn new(goals: &Goals, assumed_goal: Option<u32>) -> Self {
+        let mut impact = Self::default();
+        for change in goals.changes.iter() {
+            let old = change.old.or(assumed_goal);
+            if old.is_some_and(|g| copies(g).is_none()) || copies(change.new).is_none() {
+                impact.not_estimated += 1;
+                continue;
+            }
+            match (old.and_then(copies), copies(change.new), change.size) {
+                (Some(old), Some(new), Some(size)) => {
+                    impact.estimated += 1;
+                    if new >= old {
+                        impact.replicated_bytes += size * (new - old) as u64;
+                    } else {
+                        impact.removed_bytes += size * (old - new) as u64;
+                    }
+                }
+                _ => impact.unknown += 1,
+            }
+        }
+        impact
+    }
+}
@@ -0,0 +1,153 @@
+use chrono::NaiveDateTime;
+
+use crate::parser::line_parser::Parser;
+
+/// The known state of an inode after a changelog operation
+#[derive(Debug, Default, Clone)]
+pub struct InodeState {
+    /// The name of the inode, if known
+    pub name: Option<String>,
+    /// The length of the inode, if known
+    pub size: Option<u64>,
+    /// The permission bits of the inode, if known
+    pub mode: Option<u32>,
+    /// The owner uid of the inode, if known
+    pub uid: Option<u32>,
+    /// The owner gid of the inode, if known
+    pub gid: Option<u32>,
+    /// The goal of the inode, if known
+    pub goal: Option<u32>,
+    /// The number of names of the inode, if known
+    pub links: Option<u64>,
+    /// The path the inode points to, if it is a symlink
+    pub symlink_target: Option<String>,
+}
+
+/// A single changelog operation touching the inode
+#[derive(Debug)]
+pub struct HistoryEntry {
+    /// The timestamp of the operation
+    pub timestamp: NaiveDateTime,
+    /// The operation as written in the changelog, without the id and timestamp
+    pub operation: String,
+    /// The state of the inode after the operation
+    pub state: InodeState,
+}
+
+/// All operations on one generation of the inode number, i.e. one file or directory
+#[derive(Debug, Default)]
+pub struct Generation {
+    /// The generation, counted the same way as `Inode::generation`: the number of times the
+    /// inode number was created, 0 if it was created before the analyzed range
+    pub generation: u64,
+    /// The operations in the order they appear in the changelog
+    pub entries: Vec<HistoryEntry>,
+}
+
+/// History of all operations touching a single inode number. Inode numbers are reused after
+/// deletion, so the history is split into generations, each starting with the creation of the
+/// inode.
+#[derive(Debug)]
+pub struct InodeHistory {
+    /// The inode number to record the history for
+    pub inode: u64,
+    /// The generations of the inode number, the first one may have been created before the
+    /// analyzed range
+    pub generations: Vec<Generation>,
+    /// The current state of the inode
+    state: InodeState,
+}
+
+impl InodeHistory {
+    /// Create an empty history for the given inode number
+    pub fn new(inode: u64) -> Self {
+        Self {
+            inode,
+            generations: vec![Generation::default()],
+            state: InodeState::default(),
+        }
+    }
+
+    /// Record an operation if it references the inode, either as its target or as a parent
+    /// directory.
+    ///
+    /// # Errors
+    /// It may return an error if parsing the operation fails.
+    pub fn record(&mut self, parse: &Parser) -> Result<(), &'static str> {
+        if !parse.referenced_inodes().contains(&self.inode) {
+            return Ok(());
+        }
+        if parse.target_inode() == Some(self.inode) {
+            self.update_state(parse)?;
+        }
+        let operation = parse
+            .line
+            .split_once('|')
+            .map_or(parse.line, |(_, operation)| operation);
+        self.generations
+            .last_mut()
+            .expect("History always has a generation")
+            .entries
+            .push(HistoryEntry {
+                timestamp: parse.timestamp,
+                operation: operation.to_string(),
+                state: self.state.clone(),
+            });
+        Ok(())
+    }
+
+    /// Update the state of the inode from an operation targeting it, starting a new generation
+    /// if the inode number is created again.
+    fn update_state(&mut self, parse: &Parser) -> Result<(), &'static str> {
+        match parse.operation.as_str() {
+            "CREATE" | "SYMLINK" => {
+                let generation = self.generations.last().map_or(0, |g| g.generation) + 1;
+                match self.generations.last_mut() {
+                    Some(last) if last.entries.is_empty() => last.generation = generation,
+                    _ => self.generations.push(Generation {
+                        generation,
+                        entries: Vec::new(),
+                    }),
+                }
+                self.state = match parse.operation.as_str() {
+                    "CREATE" => {
+                        let create = parse.parse_create()?;
+                        InodeState {
+                            name: Some(create.name),
+                            size: Some(0),
+                            mode: Some(create.mode),
+                            uid: Some(create.uid),
+                            gid: Some(create.gid),
+                            links: Some(1),
+                            ..Default::default()
+                        }
+                    }
+                    _ => {
+                        let symlink = parse.parse_symlink()?;
+                        InodeState {
+                            name: Some(symlink.name),
+                            uid: Some(symlink.uid),
+                            gid: Some(symlink.gid),
+                            links: Some(1),
+                            symlink_target: Some(symlink.target),
+                            ..Default::default()
+                        }
+                    }
+                };
+            }
+            "LINK" => self.state.links = Some(self.state.links.unwrap_or(1) + 1),
+            "UNLINK" => self.state.links = self.state.links.map(|l| l.saturating_sub(1)),
+            "ATTR" => {
+                let attr = parse.parse_attr()?;
+                self.state.mode = Some(attr.mode);
+                self.state.uid = Some(attr.uid);
+                self.state.gid = Some(attr.gid);
+            }
+            "LENGTH" => self.state.size = Some(parse.parse_length()?.1),
+            "MOVE" => self.state.name = Some(parse.parse_move()?.dst_name),
+            "SETGOAL" => self.state.goal = Some(parse.parse_setgoal()?.goal),
+            _ => (),
+        }
+        Ok(())
+    }
+}
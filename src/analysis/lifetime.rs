@@ -0,0 +1,101 @@
+use std::collections::HashMap;
+
+use crate::parser::inodes::Inode;
+
+/// Files deleted within this many seconds after creation are considered short-lived
+pub const SHORT_LIVED_SECONDS: i64 = 60;
+
+/// Exclusive upper bounds (in seconds) and labels of the lifetime buckets
+const BUCKETS: [(i64, &str); 7] = [
+    (1, "<1s"),
+    (10, "<10s"),
+    (60, "<1m"),
+    (600, "<10m"),
+    (3600, "<1h"),
+    (86400, "<1d"),
+    (i64::MAX, ">1d"),
+];
+
+/// A single bucket of the lifetime distribution
+#[derive(Debug, Default)]
+pub struct LifetimeBucket {
+    /// The label of the bucket, e.g. "<1m"
+    pub label: &'static str,
+    /// The number of files in the bucket
+    pub count: u64,
+}
+
+/// Short-lived files grouped by their name suffix
+#[derive(Debug, Default)]
+pub struct SuffixLifetime {
+    /// The name suffix (extension), or an empty string for names without one
+    pub suffix: String,
+    /// The number of short-lived files with this suffix
+    pub count: u64,
+    /// The amount of data written to the short-lived files with this suffix
+    pub written: u64,
+}
+
+/// Lifetime analysis of files that were both created and deleted in the analyzed range
+#[derive(Debug)]
+pub struct Lifetimes {
+    /// The lifetime distribution
+    pub buckets: Vec<LifetimeBucket>,
+    /// The number of short-lived files
+    pub short_lived: u64,
+    /// The amount of data written to short-lived files
+    pub short_lived_written: u64,
+    /// Short-lived files grouped by suffix, sorted by count in descending order
+    pub suffixes: Vec<SuffixLifetime>,
+}
+
+impl Lifetimes {
+    /// Create the lifetime analysis from the given inodes. Only files with a known lifetime are
+    /// counted.
+    pub fn new(inodes: &[Inode]) -> Self {
+        let mut buckets: Vec<LifetimeBucket> = BUCKETS
+            .iter()
+            .map(|(_, label)| LifetimeBucket {
+                label,
+                ..Default::default()
+            })
+            .collect();
+        let mut short_lived = 0;
+        let mut short_lived_written = 0;
+        let mut suffixes: HashMap<&str, SuffixLifetime> = HashMap::new();
+
+        for inode in inodes.iter().filter(|i| i.is_file()) {
+            let Some(lifetime) = inode.lifetime() else {
+                continue;
+            };
+            let seconds = lifetime.num_seconds();
+            let index = BUCKETS
+                .iter()
+                .position(|(bound, _)| seconds < *bound)
+                .unwrap_or(BUCKETS.len() - 1);
+            buckets[index].count += 1;
+
+            if seconds < SHORT_LIVED_SECONDS {
+                short_lived += 1;
+                short_lived_written += inode.written;
+                let suffix = inode.extension().unwrap_or("");
+                let entry = suffixes.entry(suffix).or_insert(SuffixLifetime {
+                    suffix: suffix.to_string(),
+                    ..Default::default()
+                });
+                entry.count += 1;
+                entry.written += inode.written;
+            }
+        }
+
+        let mut suffixes: Vec<SuffixLifetime> = suffixes.into_values().collect();
+        suffixes.sort_by(|a, b| b.count.cmp(&a.count).then(a.suffix.cmp(&b.suffix)));
+
+        Self {
+            buckets,
+            short_lived,
+            short_lived_written,
+            suffixes,
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use std::collections::HashMap;
+
+use chrono::TimeDelta;
+
+use super::{percentile, PERCENTILES};
+use crate::parser::chunks::{Chunk, Chunks, LockHold};
+
+/// The summed lock hold time of all chunks of an inode
+#[derive(Debug)]
+pub struct InodeLocks {
+    /// The inode number
+    pub inode: u64,
+    /// The generation of the inode
+    pub generation: u64,
+    /// The number of locks released
+    pub count: u64,
+    /// The summed hold time of all locks
+    pub total: TimeDelta,
+    /// The longest hold time of a single lock
+    pub max: TimeDelta,
+}
+
+/// Analysis of the chunk lock hold times, from pairing WRITE and TRUNC with UNLOCK operations
+#[derive(Debug)]
+pub struct LockDurations<'a> {
+    /// The number of released locks
+    pub count: usize,
+    /// The hold time for each of the `PERCENTILES`
+    pub percentiles: Vec<(u32, TimeDelta)>,
+    /// The longest hold time
+    pub max: TimeDelta,
+    /// The longest lock holds, in descending order
+    pub longest: Vec<&'a LockHold>,
+    /// The inodes with the longest summed hold times, in descending order
+    pub inodes: Vec<InodeLocks>,
+    /// The chunks still locked at the end of the analyzed range
+    pub never_unlocked: Vec<&'a Chunk>,
+}
+
+impl<'a> LockDurations<'a> {
+    /// Create the lock analysis from the chunks. The top lists have at most `count` entries.
+    pub fn new(chunks: &'a Chunks, count: usize) -> Self {
+        let mut durations: Vec<TimeDelta> = chunks.lock_holds.iter().map(|h| h.duration).collect();
+        durations.sort();
+        let percentiles = PERCENTILES
+            .iter()
+            .map(|p| (*p, percentile(&durations, *p)))
+            .collect();
+
+        let mut longest: Vec<&LockHold> = chunks.lock_holds.iter().collect();
+        longest.sort_by(|a, b| b.duration.cmp(&a.duration).then(a.locked.cmp(&b.locked)));
+        longest.truncate(count);
+
+        let mut inodes: HashMap<(u64, u64), InodeLocks> = HashMap::new();
+        for hold in chunks.lock_holds.iter() {
+            let entry = inodes
+                .entry((hold.inode, hold.generation))
+                .or_insert(InodeLocks {
+                    inode: hold.inode,
+                    generation: hold.generation,
+                    count: 0,
+                    total: TimeDelta::zero(),
+                    max: TimeDelta::zero(),
+                });
+            entry.count += 1;
+            entry.total += hold.duration;
+            entry.max = entry.max.max(hold.duration);
+        }
+        let mut inodes: Vec<InodeLocks> = inodes.into_values().collect();
+        inodes.sort_by(|a, b| {
+            b.total
+                .cmp(&a.total)
+                .then(a.inode.cmp(&b.inode))
+                .then(a.generation.cmp(&b.generation))
+        });
+        inodes.truncate(count);
+
+        Self {
+            count: durations.len(),
+            percentiles,
+            max: durations.last().copied().unwrap_or_else(TimeDelta::zero),
+            longest,
+            inodes,
+            never_unlocked: chunks.still_locked(),
+        }
+    }
+}
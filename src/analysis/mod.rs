@@ -0,0 +1,26 @@
+pub mod anomalies;
+pub mod deleted;
+pub mod extensions;
+pub mod goals;
+pub mod history;
+pub mod lifetime;
+pub mod locks;
+pub mod open_files;
+pub mod owners;
+pub mod size_histogram;
+pub mod top;
+pub mod who_touched;
+
+use chrono::TimeDelta;
+
+/// The percentiles of durations to report
+pub const PERCENTILES: [u32; 3] = [50, 90, 99];
+
+/// Return the nearest-rank percentile of sorted durations, zero if there are none
+fn percentile(sorted: &[TimeDelta], percentile: u32) -> TimeDelta {
+    if sorted.is_empty() {
+        return TimeDelta::zero();
+    }
+    let rank = (percentile as usize * sorted.len()).div_ceil(100);
+    sorted[rank.saturating_sub(1)]
+}
@@ -0,0 +1,82 @@
+use std::collections::HashMap;
+
+use chrono::TimeDelta;
+
+use super::{percentile, PERCENTILES};
+use crate::parser::sessions::{OpenFile, Sessions};
+
+/// The number of files a session still has open at the end of the analyzed range
+#[derive(Debug)]
+pub struct SessionOpenFiles {
+    /// The session id
+    pub session: u64,
+    /// The number of files still open
+    pub count: usize,
+}
+
+/// Analysis of the open files, from pairing ACQUIRE and RELEASE operations
+#[derive(Debug)]
+pub struct OpenFiles<'a> {
+    /// The number of closed file handles with a known open duration
+    pub count: usize,
+    /// The open duration for each of the `PERCENTILES`
+    pub percentiles: Vec<(u32, TimeDelta)>,
+    /// The longest open duration
+    pub max: TimeDelta,
+    /// The closed file handles with the longest open durations, in descending order
+    pub longest: Vec<&'a OpenFile>,
+    /// The file handles still open at the end of the analyzed range, oldest first
+    pub still_open: Vec<&'a OpenFile>,
+    /// The sessions holding the still open file handles, by number of open files in descending
+    /// order
+    pub sessions: Vec<SessionOpenFiles>,
+}
+
+impl<'a> OpenFiles<'a> {
+    /// Create the open file analysis from the sessions. The list of longest open durations has at
+    /// most `count` entries.
+    pub fn new(sessions: &'a Sessions, count: usize) -> Self {
+        let mut durations: Vec<TimeDelta> = sessions
+            .closed
+            .iter()
+            .filter_map(|h| h.duration())
+            .collect();
+        durations.sort();
+        let percentiles = PERCENTILES
+            .iter()
+            .map(|p| (*p, percentile(&durations, *p)))
+            .collect();
+
+        let mut longest: Vec<&OpenFile> = sessions
+            .closed
+            .iter()
+            .filter(|h| h.duration().is_some())
+            .collect();
+        longest.sort_by(|a, b| {
+            b.duration()
+                .cmp(&a.duration())
+                .then(a.opened.cmp(&b.opened))
+        });
+        longest.truncate(count);
+
+        let still_open = sessions.still_open();
+        let mut per_session: HashMap<u64, usize> = HashMap::new();
+        for handle in still_open.iter() {
+            *per_session.entry(handle.session).or_insert(0) += 1;
+        }
+        let mut per_session: Vec<SessionOpenFiles> = per_session
+            .into_iter()
+            .map(|(session, count)| SessionOpenFiles { session, count })
+            .collect();
+        per_session.sort_by(|a, b| b.count.cmp(&a.count).then(a.session.cmp(&b.session)));
+
+        Self {
+            count: durations.len(),
+            percentiles,
+            max: durations.last().copied().unwrap_or_else(TimeDelta::zero),
+            longest,
+            still_open,
+            sessions: per_session,
+        }
+    }
+}
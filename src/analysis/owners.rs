@@ -0,0 +1,122 @@
+use std::collections::HashMap;
+
+use crate::parser::inodes::Inode;
+
+/// Statistics of the inodes owned by a single uid or gid
+#[derive(Debug, Default)]
+pub struct OwnerStats {
+    /// The uid or gid
+    pub id: u32,
+    /// The number of files created
+    pub files_created: u64,
+    /// The number of directories created
+    pub dirs_created: u64,
+    /// The number of inodes deleted
+    pub deleted: u64,
+    /// The amount of data written to the inodes
+    pub written: u64,
+    /// The change of the number of inodes, i.e. the created minus the deleted inodes
+    pub inode_growth: i64,
+    /// The change of the used data, i.e. the net size change of all inodes minus the last known
+    /// length of the deleted ones
+    pub byte_growth: i64,
+}
+
+/// Statistics per user and per group, based on the latest known owner of each inode
+#[derive(Debug)]
+pub struct Owners {
+    /// Statistics per uid, sorted by written data in descending order
+    pub users: Vec<OwnerStats>,
+    /// Statistics per gid, sorted by written data in descending order
+    pub groups: Vec<OwnerStats>,
+    /// The number of inodes with an unknown owner, e.g. created before the analyzed range
+    pub unknown_owner: u64,
+    /// The amount of data written to inodes with an unknown owner
+    pub unknown_owner_written: u64,
+}
+
+impl Owners {
+    /// Create the owner statistics from the given inodes
+    pub fn new(inodes: &[Inode]) -> Self {
+        let mut users: HashMap<u32, OwnerStats> = HashMap::new();
+        let mut groups: HashMap<u32, OwnerStats> = HashMap::new();
+        let mut unknown_owner = 0;
+        let mut unknown_owner_written = 0;
+
+        for inode in inodes.iter() {
+            match (inode.uid, inode.gid) {
+                (Some(uid), Some(gid)) => {
+                    add_inode(&mut users, uid, inode);
+                    add_inode(&mut groups, gid, inode);
+                }
+                _ => {
+                    unknown_owner += 1;
+                    unknown_owner_written += inode.written;
+                }
+            }
+        }
+
+        Self {
+            users: sorted(users),
+            groups: sorted(groups),
+            unknown_owner,
+            unknown_owner_written,
+        }
+    }
+}
+
+/// Parse a passwd file into a map from uid to user name. Malformed lines are skipped.
+///
+/// # Arguments
+/// * `content` - The content of the passwd file
+pub fn parse_passwd(content: &str) -> HashMap<u32, String> {
+    content
+        .lines()
+        .filter(|line| !line.starts_with('#'))
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split(':').collect();
+            let uid = fields.get(2)?.parse().ok()?;
+            Some((uid, fields[0].to_string()))
+        })
+        .collect()
+}
+
+/// Resolve a user given as a uid or as a user name from the passwd entries
+pub fn resolve_user(user: &str, users: &HashMap<u32, String>) -> Option<u32> {
+    user.parse().ok().or_else(|| {
+        users
+            .iter()
+            .find(|(_, name)| name.as_str() == user)
+            .map(|(uid, _)| *uid)
+    })
+}
+
+/// Add an inode to the statistics of an owner
+fn add_inode(owners: &mut HashMap<u32, OwnerStats>, id: u32, inode: &Inode) {
+    let stats = owners.entry(id).or_insert(OwnerStats {
+        id,
+        ..Default::default()
+    });
+    if inode.created.is_some() {
+        match inode.node_type {
+            Some('f') => stats.files_created += 1,
+            Some('d') => stats.dirs_created += 1,
+            _ => (),
+        }
+        stats.inode_growth += 1;
+    }
+    stats.byte_growth += inode.net_size_change();
+    if inode.deleted.is_some() {
+        stats.deleted += 1;
+        stats.inode_growth -= 1;
+        stats.byte_growth -= inode.last_known_length as i64;
+    }
+    stats.written += inode.written;
+}
+
+/// Sort owner statistics by written data in descending order
+fn sorted(owners: HashMap<u32, OwnerStats>) -> Vec<OwnerStats> {
+    let mut owners: Vec<OwnerStats> = owners.into_values().collect();
+    owners.sort_by(|a, b| b.written.cmp(&a.written).then(a.id.cmp(&b.id)));
+    owners
+}
@@ -0,0 +1,65 @@
+use crate::parser::inodes::Inode;
+
+/// Exclusive upper bounds and labels of the non-empty file size buckets. Each bucket is 16 times
+/// larger than the previous one, the last bucket holds everything from 1 TB upwards, 1 TB included.
+const BUCKETS: [(u64, &str); 9] = [
+    (1 << 12, "<4K"),
+    (1 << 16, "<64K"),
+    (1 << 20, "<1M"),
+    (1 << 24, "<16M"),
+    (1 << 28, "<256M"),
+    (1 << 32, "<4G"),
+    (1 << 36, "<64G"),
+    (1 << 40, "<1T"),
+    (u64::MAX, "≥1T"),
+];
+
+/// A single bucket of the file size histogram
+#[derive(Debug, Default)]
+pub struct SizeBucket {
+    /// The label of the bucket, e.g. "<64K"
+    pub label: &'static str,
+    /// The number of files in the bucket
+    pub count: u64,
+    /// The sum of the sizes of the files in the bucket
+    pub bytes: u64,
+}
+
+/// Histogram of the final file sizes, based on the last known length of each file that was not
+/// deleted
+#[derive(Debug)]
+pub struct SizeHistogram {
+    /// The buckets, starting with the bucket for empty files
+    pub buckets: Vec<SizeBucket>,
+}
+
+impl SizeHistogram {
+    /// Create a histogram from the given inodes. Only inodes considered files that still exist at
+    /// the end of the changelog are counted.
+    pub fn new(inodes: &[Inode]) -> Self {
+        let mut buckets = vec![SizeBucket {
+            label: "0",
+            ..Default::default()
+        }];
+        buckets.extend(BUCKETS.iter().map(|(_, label)| SizeBucket {
+            label,
+            ..Default::default()
+        }));
+
+        for inode in inodes.iter().filter(|i| i.is_file() && i.deleted.is_none()) {
+            let length = inode.last_known_length;
+            let index = if length == 0 {
+                0
+            } else {
+                1 + BUCKETS
+                    .iter()
+                    .position(|(bound, _)| length < *bound)
+                    .unwrap_or(BUCKETS.len() - 1)
+            };
+            buckets[index].count += 1;
+            buckets[index].bytes += length;
+        }
+
+        Self { buckets }
+    }
+}
@@ -0,0 +1,37 @@
+use crate::parser::inodes::Inode;
+
+/// The inodes with the highest written bytes, operation counts and rewrite cycles
+#[derive(Debug)]
+pub struct TopInodes<'a> {
+    /// Inodes sorted by the estimated written bytes in descending order
+    pub by_written: Vec<&'a Inode>,
+    /// Inodes sorted by the number of operations in descending order
+    pub by_operations: Vec<&'a Inode>,
+    /// Inodes sorted by the number of rewrite cycles in descending order
+    pub by_rewrites: Vec<&'a Inode>,
+}
+
+impl<'a> TopInodes<'a> {
+    /// Create the top lists from the given inodes. Each list has at most `count` entries and
+    /// leaves out inodes with a value of zero.
+    pub fn new(inodes: &'a [Inode], count: usize) -> Self {
+        Self {
+            by_written: top_by(inodes, count, |i| i.written),
+            by_operations: top_by(inodes, count, |i| i.operations),
+            by_rewrites: top_by(inodes, count, |i| i.rewrites),
+        }
+    }
+}
+
+/// Return the `count` inodes with the highest non-zero value for the given key
+fn top_by(inodes: &[Inode], count: usize, key: impl Fn(&Inode) -> u64) -> Vec<&Inode> {
+    let mut top: Vec<&Inode> = inodes.iter().filter(|i| key(i) > 0).collect();
+    top.sort_by(|a, b| {
+        key(b)
+            .cmp(&key(a))
+            .then(a.inode.cmp(&b.inode))
+            .then(a.generation.cmp(&b.generation))
+    });
+    top.truncate(count);
+    top
+}
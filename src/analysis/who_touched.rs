@@ -0,0 +1,174 @@
+use std::collections::HashSet;
+
+use chrono::NaiveDateTime;
+
+use crate::parser::{line_parser::Parser, namespace::Namespace, sessions::Sessions};
+
+/// A single operation touching an entry matching the pattern
+#[derive(Debug)]
+pub struct TouchEvent {
+    /// The timestamp of the operation
+    pub timestamp: NaiveDateTime,
+    /// The operation name, e.g. CREATE or UNLINK, or REPLACE for an entry removed by a MOVE onto
+    /// its name
+    pub operation: String,
+    /// The inode the operation was applied to
+    pub inode: u64,
+    /// The path of the entry, or the source and destination paths of a MOVE
+    pub path: String,
+    /// The session that had the file or its parent directory open, if any
+    pub session: Option<u64>,
+}
+
+/// Forensic lookup of every CREATE, MOVE, ATTR, WRITE and UNLINK touching entries whose path or
+/// name matches a glob pattern. Inodes that matched once are followed through renames until they
+/// are unlinked or replaced by a MOVE onto their name.
+#[derive(Debug)]
+pub struct WhoTouched {
+    /// The glob pattern, matched against the whole path if it contains a '/' and against the
+    /// name otherwise
+    pub pattern: String,
+    /// The matching operations in the order they appear in the changelog
+    pub events: Vec<TouchEvent>,
+    /// The inodes that matched the pattern and are followed
+    followed: HashSet<u64>,
+}
+
+impl WhoTouched {
+    /// Create an empty lookup for the given glob pattern
+    pub fn new(pattern: String) -> Self {
+        Self {
+            pattern,
+            events: Vec::new(),
+            followed: HashSet::new(),
+        }
+    }
+
+    /// Record an operation if it touches a matching or followed entry. It must be called before
+    /// the namespace is updated by the operation, so the paths before the operation are known.
+    ///
+    /// # Errors
+    /// It may return an error if parsing the operation fails.
+    pub fn record(
+        &mut self,
+        parse: &Parser,
+        namespace: &Namespace,
+        sessions: &Sessions,
+    ) -> Result<(), &'static str> {
+        let mut replaced = None;
+        let (inode, parent, path, matched) = match parse.operation.as_str() {
+            "CREATE" => {
+                let create = parse.parse_create()?;
+                let inode = parse
+                    .inode
+                    .ok_or("Could not find inode in create operation")?;
+                let path = namespace.child_path(create.parent, &create.name);
+                let matched = self.matches(&path);
+                (inode, Some(create.parent), path, matched)
+            }
+            "MOVE" => {
+                let moved = parse.parse_move()?;
+                let src = namespace.child_path(moved.src_parent, &moved.src_name);
+                let dst = namespace.child_path(moved.dst_parent, &moved.dst_name);
+                let matched = self.matches(&src) || self.matches(&dst);
+                // A followed inode still named like the destination is replaced by the move
+                replaced = self
+                    .followed
+                    .iter()
+                    .copied()
+                    .find(|f| {
+                        *f != moved.inode
+                            && namespace.get(*f).is_some_and(|e| {
+                                e.parent == moved.dst_parent && e.name == moved.dst_name
+                            })
+                    })
+                    .map(|f| (f, moved.dst_parent, dst.clone()));
+                let path = format!("{} -> {}", src, dst);
+                (moved.inode, Some(moved.src_parent), path, matched)
+            }
+            "UNLINK" => {
+                let (parent, name) = parse.parse_unlink()?;
+                let inode = parse
+                    .inode
+                    .ok_or("Could not find inode in unlink operation")?;
+                let path = namespace.child_path(parent, &name);
+                let matched = self.matches(&path);
+                (inode, Some(parent), path, matched)
+            }
+            "ATTR" | "WRITE" => {
+                let inode = match parse.operation.as_str() {
+                    "ATTR" => parse.parse_attr()?.inode,
+                    _ => parse.parse_write()?.inode,
+                };
+                let path = namespace.path(inode);
+                let matched = namespace.get(inode).is_some() && self.matches(&path);
+                (inode, namespace.get(inode).map(|e| e.parent), path, matched)
+            }
+            _ => return Ok(()),
+        };
+        if matched || self.followed.contains(&inode) {
+            if parse.operation == "UNLINK" {
+                self.followed.remove(&inode);
+            } else {
+                self.followed.insert(inode);
+            }
+            let session = match parse.operation.as_str() {
+                "CREATE" => None,
+                _ => sessions.opener(inode),
+            }
+            .or_else(|| parent.and_then(|p| sessions.opener(p)));
+            self.events.push(TouchEvent {
+                timestamp: parse.timestamp,
+                operation: parse.operation.clone(),
+                inode,
+                path,
+                session,
+            });
+        }
+        if let Some((inode, parent, path)) = replaced {
+            self.followed.remove(&inode);
+            self.events.push(TouchEvent {
+                timestamp: parse.timestamp,
+                operation: "REPLACE".to_string(),
+                inode,
+                path,
+                session: sessions.opener(inode).or_else(|| sessions.opener(parent)),
+            });
+        }
+        Ok(())
+    }
+
+    /// Whether a path matches the pattern
+    fn matches(&self, path: &str) -> bool {
+        if self.pattern.contains('/') {
+            glob_match(&self.pattern, path)
+        } else {
+            glob_match(&self.pattern, path.rsplit('/').next().unwrap_or(path))
+        }
+    }
+}
+
+/// Whether a text matches a glob pattern, where '*' matches any sequence of characters,
+/// including '/', and '?' matches a single character
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, t));
+            p += 1;
+        } else if let Some((star, matched)) = backtrack {
+            p = star + 1;
+            t = matched + 1;
+            backtrack = Some((star, matched + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '*')
+}
@@ -1,15 +1,48 @@
+pub mod analysis;
 pub mod output;
 pub mod parser;
 
 use std::{
     collections::HashMap,
     fs::File,
-    io::{BufRead, BufReader},
+    io::{BufRead, BufReader, Error, ErrorKind},
     process::exit,
 };
 
-use output::print_result;
-use parser::{inodes::Inodes, line_parser::Parser};
+use analysis::{
+    anomalies::Anomalies,
+    deleted::DeletedFiles,
+    extensions::Extensions,
+    goals::GoalImpact,
+    history::InodeHistory,
+    locks::LockDurations,
+    open_files::OpenFiles,
+    owners::{parse_passwd, resolve_user, Owners},
+    top::TopInodes,
+    who_touched::WhoTouched,
+};
+use output::{
+    print_anomalies, print_audit, print_deleted, print_extensions, print_goals, print_history,
+    print_hot_dirs, print_locks, print_open_files, print_owners, print_quotas, print_renames,
+    print_result, print_sessions, print_top, print_trash, print_who_touched, print_xattrs,
+};
+use parser::{
+    activity::{Activity, ActivityEvent},
+    attributes::{AttributeChanges, Attributes},
+    chunks::Chunks,
+    directories::Directories,
+    goals::{GoalTarget, Goals},
+    inodes::{extension, Inodes},
+    line_parser::Parser,
+    namespace::Namespace,
+    node_types::{NodeType, NodeTypes},
+    quotas::Quotas,
+    renames::Renames,
+    sessions::Sessions,
+    snapshots::{SnapshotEvent, Snapshots},
+    trash::{Trash, TrashEntry},
+    xattrs::Xattrs,
+};
 
 /// Struct to hold the start and end timestamps
 /// The start and end timestamps are used to determine the range of logs to read
@@ -27,6 +60,60 @@ pub struct TimestampRange {
     pub end_is_set: bool,
 }
 
+/// The report to print after the changelog analysis
+#[derive(Debug, Default, Clone)]
+pub enum Mode {
+    /// Print the summary of all operations
+    #[default]
+    Summary,
+    /// Print the given number of inodes with the most written bytes, operations and rewrites
+    Top(usize),
+    /// Print every operation touching the given inode number
+    History(u64),
+    /// Print the chunk lock hold times, with the given number of top offenders
+    Locks(usize),
+    /// Print the open files and their durations, with the given number of longest open files
+    OpenFiles(usize),
+    /// Print the activity of the given number of most active sessions
+    Sessions(usize),
+    /// Print the statistics per owner, with user names from the given passwd file, if any
+    Owners(Option<String>),
+    /// Print every permission and ownership change
+    Audit,
+    /// Print the applied goals and the estimated replication traffic, assuming the given goal
+    /// for inodes whose previous goal is unknown
+    Goals(Option<u32>),
+    /// Print the files moved to trash, purged and restored, and the data in trash over time
+    Trash,
+    /// Print the files that can still be restored from trash, optionally only those below a path
+    /// prefix or owned by a user, with user names from a passwd file
+    Deleted {
+        /// Only list files whose path at deletion is at or below this path
+        prefix: Option<String>,
+        /// Only list files owned by this user, given as a uid or a user name
+        user: Option<String>,
+        /// The path of the passwd file used to resolve the user name given in `user`
+        passwd: Option<String>,
+    },
+    /// Print every operation touching entries whose path or name matches the given glob pattern
+    WhoTouched(String),
+    /// Print the rename statistics, the given number of atomic saves and most renamed inodes
+    Renames(usize),
+    /// Print the given number of most written extended attribute names and inodes, and every
+    /// ACL change
+    Xattrs(usize),
+    /// Print every quota change and the usage growth of the given number of fastest growing
+    /// users and groups
+    Quotas(usize),
+    /// Print the given number of directories with the most namespace operations and churn
+    HotDirs(usize),
+    /// Print the file statistics of the given number of most created name extensions
+    Extensions(usize),
+    /// Print the windows of the given length in seconds with at least the given number of
+    /// unlinks, rewrites or renames to a common extension
+    Anomalies(u64, usize),
+}
+
 /// Struct to hold the results of the changelog analysis
 #[derive(Debug, Default)]
 pub struct ChangelogResults {
@@ -34,12 +121,43 @@ pub struct ChangelogResults {
     pub op_count: HashMap<String, u64>,
     /// Inodes struct to hold both currently active and historical inodes
     pub inodes: Inodes,
+    /// Chunks struct to hold all chunks written or truncated
+    pub chunks: Chunks,
+    /// Sessions struct to hold the files opened by sessions
+    pub sessions: Sessions,
+    /// Namespace struct to resolve inodes to paths
+    pub namespace: Namespace,
+    /// AttributeChanges struct to hold all permission and ownership changes
+    pub attribute_changes: AttributeChanges,
+    /// Goals struct to hold the goals of inodes and all goal changes
+    pub goals: Goals,
+    /// Trash struct to hold the files moved to trash and their purges and restores
+    pub trash: Trash,
+    /// Renames struct to hold the MOVE statistics, path lineages and atomic saves
+    pub renames: Renames,
+    /// Snapshots struct to hold the SNAPSHOT operations and their estimated size
+    pub snapshots: Snapshots,
+    /// Xattrs struct to hold the extended attribute and ACL changes
+    pub xattrs: Xattrs,
+    /// Quotas struct to hold the quota changes
+    pub quotas: Quotas,
+    /// Directories struct to hold the namespace operations by parent directory
+    pub directories: Directories,
+    /// Unlinks, rewrites and extension changes for the detection of mass operations, only
+    /// recorded if set before parsing
+    pub activity: Option<Activity>,
     /// Count of files created
     pub file_count: u64,
     /// Count of directories created
     pub dir_count: u64,
     /// Count of inodes created
     pub inode_created_count: u64,
+    /// NodeTypes struct to hold the count of created inodes by node type
+    pub node_types: NodeTypes,
+    /// History of a single inode number, only recorded if set before parsing
+    pub history: Option<InodeHistory>,
+    /// Operations touching paths matching a pattern, only recorded if set before parsing
+    pub who_touched: Option<WhoTouched>,
 }
 
 /// Run the main logic of the program
@@ -47,13 +165,23 @@ pub struct ChangelogResults {
 /// # Arguments
 /// * `args` - The list of files to read from
 /// * `timeline` - The timeline struct to update. mut is needed because if start and end are not
-/// set, they are set to the first and last timestamp found
-pub fn run(mut args: Vec<String>, mut timeline: TimestampRange) -> std::io::Result<()> {
+///   set, they are set to the first and last timestamp found
+/// * `mode` - The report to print
+pub fn run(mut args: Vec<String>, mut timeline: TimestampRange, mode: Mode) -> std::io::Result<()> {
     let mut results = ChangelogResults::default();
+    if let Mode::History(inode) = mode {
+        results.history = Some(InodeHistory::new(inode));
+    }
+    if let Mode::WhoTouched(pattern) = &mode {
+        results.who_touched = Some(WhoTouched::new(pattern.clone()));
+    }
+    if let Mode::Anomalies(..) = mode {
+        results.activity = Some(Activity::new());
+    }
 
     args.sort_by_key(|s| {
         s.split('.')
-            .last()
+            .next_back()
             .unwrap_or("0")
             .parse::<i32>()
             .unwrap_or(0)
@@ -86,11 +214,87 @@ pub fn run(mut args: Vec<String>, mut timeline: TimestampRange) -> std::io::Resu
     count_vec.sort_by(|a, b| b.1.cmp(a.1));
 
     results.inodes.drain_active();
-    print_result(&timeline, count_vec, &results);
+    match mode {
+        Mode::Summary => print_result(&timeline, count_vec, &results),
+        Mode::Top(count) => print_top(&TopInodes::new(&results.inodes.all, count)),
+        Mode::History(_) => {
+            if let Some(history) = &results.history {
+                print_history(history);
+            }
+        }
+        Mode::Locks(count) => {
+            print_locks(&LockDurations::new(&results.chunks, count), &results, count)
+        }
+        Mode::OpenFiles(count) => {
+            print_open_files(&OpenFiles::new(&results.sessions, count), &results, count)
+        }
+        Mode::Sessions(count) => print_sessions(&results.sessions, count),
+        Mode::Owners(passwd) => {
+            print_owners(&Owners::new(&results.inodes.all), &read_passwd(passwd)?);
+        }
+        Mode::Audit => print_audit(&results.attribute_changes),
+        Mode::Goals(assumed_goal) => print_goals(
+            &results.goals,
+            &GoalImpact::new(&results.goals, assumed_goal),
+            &results,
+        ),
+        Mode::Trash => print_trash(&results.trash),
+        Mode::Deleted {
+            prefix,
+            user,
+            passwd,
+        } => {
+            let users = read_passwd(passwd)?;
+            let uid = match user {
+                Some(user) => Some(resolve_user(&user, &users).ok_or_else(|| {
+                    Error::new(ErrorKind::InvalidInput, format!("Unknown user: {}", user))
+                })?),
+                None => None,
+            };
+            print_deleted(
+                &DeletedFiles::new(&results.trash, prefix.as_deref(), uid),
+                &users,
+            );
+        }
+        Mode::Renames(count) => print_renames(&results.renames, count),
+        Mode::Xattrs(count) => print_xattrs(&results, count),
+        Mode::HotDirs(count) => print_hot_dirs(&results, count),
+        Mode::Anomalies(window, min_events) => {
+            if let Some(activity) = &results.activity {
+                print_anomalies(&Anomalies::new(
+                    activity,
+                    &results.namespace,
+                    &timeline,
+                    window,
+                    min_events,
+                ));
+            }
+        }
+        Mode::Extensions(count) => print_extensions(&Extensions::new(&results.inodes.all), count),
+        Mode::Quotas(count) => print_quotas(
+            &results.quotas,
+            &Owners::new(&results.inodes.all),
+            &timeline,
+            count,
+        ),
+        Mode::WhoTouched(_) => {
+            if let Some(who_touched) = &results.who_touched {
+                print_who_touched(who_touched);
+            }
+        }
+    }
 
     Ok(())
 }
 
+/// Read the user names from a passwd file, if given
+fn read_passwd(passwd: Option<String>) -> std::io::Result<HashMap<u32, String>> {
+    match passwd {
+        Some(path) => Ok(parse_passwd(&std::fs::read_to_string(path)?)),
+        None => Ok(HashMap::new()),
+    }
+}
+
 /// Parse a specific line from the changelog
 /// Returns true if parsing should continue, false if it should stop (because we may have passed
 /// the end date)
@@ -117,7 +321,15 @@ pub fn parse_line(
         return Ok(true);
     }
 
+    if let Some(who_touched) = results.who_touched.as_mut() {
+        who_touched.record(&parse, &results.namespace, &results.sessions)?;
+    }
     check_inode_operation(&parse, results)?;
+    check_chunk_operation(&parse, results)?;
+    check_session_operation(&parse, results)?;
+    if let Some(history) = results.history.as_mut() {
+        history.record(&parse)?;
+    }
 
     *results.op_count.entry(parse.operation).or_insert(0) += 1;
 
@@ -132,14 +344,16 @@ pub fn parse_line(
 }
 
 /// Check if the operation is an inode operation, and if so, update the inodes struct in
-/// ChangelogResults.
+/// ChangelogResults. Every operation on a tracked inode is counted on it.
 ///
 /// # Arguments
 /// * `parse` - The parser struct
 /// * `results` - The results struct to update
 ///
 /// # Errors
-/// It may return an error if parsing the LENGTH for the inode length fails.
+/// It may return an error if parsing the CREATE, SYMLINK, LINK, UNLINK, MOVE, LENGTH, TRUNC,
+/// SNAPSHOT, SETXATTR, SETACL, DELETEACL, SETQUOTA, SETGOAL, SETTRASHTIME, PURGE, UNDEL or ATTR
+/// for the inode fails.
 fn check_inode_operation(
     parse: &Parser,
     results: &mut ChangelogResults,
@@ -148,15 +362,356 @@ fn check_inode_operation(
         match parse.operation.as_str() {
             "CREATE" => {
                 results.inodes.append(inode, Some(parse.timestamp));
-                parse.parse_line_for_dir_file(&mut results.dir_count, &mut results.file_count);
+                let create = parse.parse_create()?;
+                results
+                    .namespace
+                    .insert(inode, create.parent, create.name.clone());
+                results.attribute_changes.create(
+                    inode,
+                    Attributes {
+                        mode: create.mode,
+                        uid: create.uid,
+                        gid: create.gid,
+                    },
+                );
+                if let Some(i) = results.inodes.active_mut(inode) {
+                    i.node_type = Some(create.node_type);
+                    i.name = Some(create.name);
+                    i.mode = Some(create.mode);
+                    i.uid = Some(create.uid);
+                    i.gid = Some(create.gid);
+                }
+                match results.node_types.create(create.node_type) {
+                    NodeType::File => results.file_count += 1,
+                    NodeType::Directory => results.dir_count += 1,
+                    _ => (),
+                }
+                results.directories.add(create.parent);
+                results.trash.inherit(inode, create.parent);
+                results.renames.create(inode);
+                results.inode_created_count += 1;
+            }
+            "UNLINK" => {
+                let (parent, name) = parse.parse_unlink()?;
+                results.directories.remove(parent);
+                if let Some(activity) = results.activity.as_mut() {
+                    activity.unlink(ActivityEvent {
+                        timestamp: parse.timestamp,
+                        inode,
+                        generation: results.inodes.current_generation(inode),
+                        parent: Some(parent),
+                    });
+                }
+                unlink_name(results, inode, parent, name, parse.timestamp);
+            }
+            "SYMLINK" => {
+                results.inodes.append(inode, Some(parse.timestamp));
+                let symlink = parse.parse_symlink()?;
+                results
+                    .namespace
+                    .insert(inode, symlink.parent, symlink.name.clone());
+                if let Some(i) = results.inodes.active_mut(inode) {
+                    i.node_type = Some('l');
+                    i.name = Some(symlink.name);
+                    i.uid = Some(symlink.uid);
+                    i.gid = Some(symlink.gid);
+                    i.symlink_target = Some(symlink.target);
+                }
+                results.node_types.create('l');
+                results.directories.add(symlink.parent);
+                results.trash.inherit(inode, symlink.parent);
+                results.renames.create(inode);
                 results.inode_created_count += 1;
             }
-            "UNLINK" => results.inodes.delete(inode, Some(parse.timestamp)),
+            "MOVE" => {
+                let moved = parse.parse_move()?;
+                let src_path = results
+                    .namespace
+                    .child_path(moved.src_parent, &moved.src_name);
+                let dst_path = results
+                    .namespace
+                    .child_path(moved.dst_parent, &moved.dst_name);
+                let replaced = results
+                    .namespace
+                    .lookup(moved.dst_parent, &moved.dst_name)
+                    .filter(|replaced| *replaced != inode);
+                if let Some(replaced) = replaced {
+                    unlink_name(
+                        results,
+                        replaced,
+                        moved.dst_parent,
+                        moved.dst_name.clone(),
+                        parse.timestamp,
+                    );
+                }
+                if let Some(activity) = results.activity.as_mut() {
+                    activity.rename(
+                        ActivityEvent {
+                            timestamp: parse.timestamp,
+                            inode,
+                            generation: results.inodes.current_generation(inode),
+                            parent: Some(moved.dst_parent),
+                        },
+                        extension(&moved.src_name),
+                        extension(&moved.dst_name),
+                    );
+                }
+                results
+                    .directories
+                    .rename(moved.src_parent, moved.dst_parent, replaced.is_some());
+                let written = results
+                    .inodes
+                    .active(inode)
+                    .is_some_and(|i| i.created.is_some() && i.written > 0);
+                results.renames.record(
+                    &moved,
+                    src_path,
+                    dst_path,
+                    replaced,
+                    written,
+                    parse.timestamp,
+                );
+                results.namespace.rename(
+                    inode,
+                    moved.src_parent,
+                    &moved.src_name,
+                    moved.dst_parent,
+                    moved.dst_name,
+                );
+            }
+            _ => (),
+        }
+    } else {
+        match parse.operation.as_str() {
+            "LENGTH" => {
+                let (inode, length) = parse.parse_length()?;
+                if let Some(activity) = results.activity.as_mut() {
+                    activity.length(
+                        ActivityEvent {
+                            timestamp: parse.timestamp,
+                            inode,
+                            generation: results.inodes.current_generation(inode),
+                            parent: results.namespace.get(inode).map(|e| e.parent),
+                        },
+                        length,
+                    );
+                }
+                let written = results.inodes.update_length(inode, length);
+                results.sessions.record_written_bytes(inode, written);
+            }
+            "TRUNC" => results.inodes.truncate(parse.parse_trunc()?.inode),
+            "LINK" => {
+                let link = parse.parse_link()?;
+                results.inodes.link(link.inode);
+                results.directories.add(link.parent);
+                results.namespace.link(link.inode, link.parent, link.name);
+            }
+            "SNAPSHOT" => {
+                let snapshot = parse.parse_snapshot()?;
+                let subtree = results.namespace.subtree(snapshot.source);
+                let bytes = subtree
+                    .iter()
+                    .filter_map(|i| results.inodes.active(*i))
+                    .filter(|i| i.is_file())
+                    .map(|i| i.last_known_length)
+                    .sum();
+                results.snapshots.record(SnapshotEvent {
+                    timestamp: parse.timestamp,
+                    source: snapshot.source,
+                    source_path: results.namespace.path(snapshot.source),
+                    path: results
+                        .namespace
+                        .child_path(snapshot.dst_parent, &snapshot.name),
+                    can_overwrite: snapshot.can_overwrite,
+                    inodes: subtree.len() as u64,
+                    bytes,
+                });
+            }
+            "SETGOAL" => {
+                let set_goal = parse.parse_setgoal()?;
+                let inodes = if set_goal.recursive {
+                    results.namespace.subtree(set_goal.inode)
+                } else {
+                    vec![set_goal.inode]
+                };
+                let mut targets = Vec::with_capacity(inodes.len());
+                for inode in inodes {
+                    let mut target = GoalTarget {
+                        inode,
+                        size: None,
+                        directory: false,
+                    };
+                    if let Some(i) = results.inodes.active_mut(inode) {
+                        i.goal = Some(set_goal.goal);
+                        target.size = i.is_file().then_some(i.last_known_length);
+                        target.directory = i.node_type == Some('d');
+                    }
+                    targets.push(target);
+                }
+                results.goals.set(&set_goal, &targets, parse.timestamp);
+            }
+            "SETXATTR" => results.xattrs.set(parse.parse_setxattr()?),
+            "SETQUOTA" => results.quotas.set(parse.parse_setquota()?, parse.timestamp),
+            "SETACL" | "DELETEACL" => {
+                let acl = parse.parse_acl()?;
+                let path = results.namespace.path(acl.inode);
+                results.xattrs.acl(acl, path, parse.timestamp);
+            }
+            "SETTRASHTIME" => {
+                let set_trash_time = parse.parse_settrashtime()?;
+                let inodes = if set_trash_time.recursive {
+                    results.namespace.subtree(set_trash_time.inode)
+                } else {
+                    vec![set_trash_time.inode]
+                };
+                results.trash.set_trash_time(&set_trash_time, &inodes);
+            }
+            "PURGE" => results.trash.purge(parse.parse_single()?, parse.timestamp),
+            "UNDEL" => {
+                let undeleted = parse.parse_single()?;
+                if let Some(entry) = results.trash.undelete(undeleted, parse.timestamp) {
+                    results
+                        .namespace
+                        .insert(undeleted, entry.parent, entry.name.clone());
+                    results.inodes.undelete(undeleted);
+                }
+            }
+            "ATTR" => {
+                let attr = parse.parse_attr()?;
+                if let Some(i) = results.inodes.active_mut(attr.inode) {
+                    i.mode = Some(attr.mode);
+                    i.uid = Some(attr.uid);
+                    i.gid = Some(attr.gid);
+                }
+                let namespace = &results.namespace;
+                results.attribute_changes.update(
+                    attr.inode,
+                    Attributes {
+                        mode: attr.mode,
+                        uid: attr.uid,
+                        gid: attr.gid,
+                    },
+                    || namespace.path(attr.inode),
+                    parse.timestamp,
+                );
+            }
             _ => (),
         }
-    } else if parse.operation.as_str() == "LENGTH" {
-        let (inode, length) = parse.parse_length()?;
-        results.inodes.update_length(inode, length)
     };
+    if let Some(target) = parse.target_inode() {
+        results.inodes.record_operation(target);
+    }
+    Ok(())
+}
+
+/// Remove a name of an inode from the namespace. If it was the last name, the inode is deleted and
+/// files are moved to trash, unless their trash time is known to be 0.
+fn unlink_name(
+    results: &mut ChangelogResults,
+    inode: u64,
+    parent: u64,
+    name: String,
+    timestamp: chrono::NaiveDateTime,
+) {
+    if !results.inodes.unlink(inode) {
+        results.namespace.unlink(inode, parent, &name);
+        return;
+    }
+    results.namespace.insert(inode, parent, name.clone());
+    let active = results.inodes.active(inode);
+    if active.is_none_or(|i| i.node_type.is_none_or(|t| t == 'f')) {
+        results.trash.unlink(TrashEntry {
+            inode,
+            generation: results.inodes.current_generation(inode),
+            path: results.namespace.path(inode),
+            parent,
+            name: name.clone(),
+            size: active.map(|i| i.last_known_length),
+            known_file: active.is_some_and(|i| i.is_file()),
+            uid: active.and_then(|i| i.uid),
+            gid: active.and_then(|i| i.gid),
+            trash_time: None,
+            deleted: timestamp,
+            purged: None,
+            undeleted: None,
+        });
+    }
+    results.namespace.unlink(inode, parent, &name);
+    results.inodes.delete(inode, Some(timestamp));
+}
+
+/// Check if the operation is a chunk operation, and if so, update the chunks struct in
+/// ChangelogResults.
+///
+/// # Arguments
+/// * `parse` - The parser struct
+/// * `results` - The results struct to update
+///
+/// # Errors
+/// It may return an error if parsing the WRITE, TRUNC, UNLOCK or NEXTCHUNKID operation fails.
+fn check_chunk_operation(
+    parse: &Parser,
+    results: &mut ChangelogResults,
+) -> Result<(), &'static str> {
+    match parse.operation.as_str() {
+        "WRITE" => {
+            let write = parse.parse_write()?;
+            let generation = results.inodes.current_generation(write.inode);
+            results.chunks.write(&write, generation, parse.timestamp);
+        }
+        "TRUNC" => {
+            let trunc = parse.parse_trunc()?;
+            let generation = results.inodes.current_generation(trunc.inode);
+            results.chunks.truncate(&trunc, generation, parse.timestamp);
+        }
+        "UNLOCK" => results
+            .chunks
+            .unlock(parse.parse_single()?, parse.timestamp),
+        "NEXTCHUNKID" => results.chunks.update_next_chunk_id(parse.parse_single()?),
+        _ => (),
+    }
+    Ok(())
+}
+
+/// Check if the operation is a session operation, and if so, update the sessions struct in
+/// ChangelogResults.
+///
+/// # Arguments
+/// * `parse` - The parser struct
+/// * `results` - The results struct to update
+///
+/// # Errors
+/// It may return an error if parsing the SESSION, ACQUIRE, RELEASE or WRITE operation fails.
+fn check_session_operation(
+    parse: &Parser,
+    results: &mut ChangelogResults,
+) -> Result<(), &'static str> {
+    match parse.operation.as_str() {
+        "SESSION" => results
+            .sessions
+            .create(parse.parse_session()?, parse.timestamp),
+        "ACQUIRE" => {
+            let (inode, session) = parse.parse_acquire()?;
+            let generation = results.inodes.current_generation(inode);
+            results
+                .sessions
+                .acquire(inode, generation, session, parse.timestamp);
+        }
+        "RELEASE" => {
+            let (inode, session) = parse.parse_acquire()?;
+            let generation = results.inodes.current_generation(inode);
+            results
+                .sessions
+                .release(inode, generation, session, parse.timestamp);
+        }
+        "WRITE" => {
+            let inode = parse.parse_write()?.inode;
+            let generation = results.inodes.current_generation(inode);
+            results
+                .sessions
+                .record_write(inode, generation, parse.timestamp);
+        }
+        _ => (),
+    }
     Ok(())
 }
@@ -1,8 +1,11 @@
 use std::process::{exit, Command};
 
 use chrono::NaiveDateTime;
-use clap::Parser;
-use saunafs_query::{run, TimestampRange};
+use clap::{Parser, Subcommand};
+use saunafs_query::{
+    analysis::anomalies::{MIN_EVENTS, WINDOW_SECONDS},
+    run, Mode, TimestampRange,
+};
 
 /// CLI parser
 /// Uses the `clap` library to parse command line arguments
@@ -11,15 +14,169 @@ use saunafs_query::{run, TimestampRange};
 #[command(version, about = "Query .sfs journal files", long_about = None)]
 struct Cli {
     /// When to start reading from the logs
-    #[arg(long, value_name = "STRING")]
+    #[arg(long, value_name = "STRING", global = true)]
     start: Option<String>,
     /// When to stop reading from the logs
-    #[arg(long, value_name = "STRING")]
+    #[arg(long, value_name = "STRING", global = true)]
     stop: Option<String>,
+    /// Report to print instead of the summary
+    #[command(subcommand)]
+    report: Option<Report>,
     /// Metadata files to read from
     files: Vec<String>,
 }
 
+/// Reports other than the default summary
+#[derive(Subcommand)]
+enum Report {
+    /// List the inodes with the most written bytes, operations and rewrites
+    Top {
+        /// Number of inodes to list
+        #[arg(short = 'n', long, default_value_t = 10)]
+        count: usize,
+        /// Metadata files to read from
+        files: Vec<String>,
+    },
+    /// Print every operation touching an inode together with its evolving state
+    History {
+        /// Inode number to print the history for
+        #[arg(long)]
+        inode: u64,
+        /// Metadata files to read from
+        files: Vec<String>,
+    },
+    /// Print chunk lock hold times from WRITE/TRUNC and UNLOCK pairs
+    Locks {
+        /// Number of top offending locks and inodes to list
+        #[arg(short = 'n', long, default_value_t = 10)]
+        count: usize,
+        /// Metadata files to read from
+        files: Vec<String>,
+    },
+    /// Print open files over time and files left open, from ACQUIRE and RELEASE pairs
+    OpenFiles {
+        /// Number of longest open and still open files to list
+        #[arg(short = 'n', long, default_value_t = 10)]
+        count: usize,
+        /// Metadata files to read from
+        files: Vec<String>,
+    },
+    /// Print files opened, inodes written and bytes written per session
+    Sessions {
+        /// Number of most active sessions to list
+        #[arg(short = 'n', long, default_value_t = 10)]
+        count: usize,
+        /// Metadata files to read from
+        files: Vec<String>,
+    },
+    /// Print files and directories created, deleted and bytes written per user and group
+    Owners {
+        /// passwd file to map uids to user names
+        #[arg(long, value_name = "FILE")]
+        passwd: Option<String>,
+        /// Metadata files to read from
+        files: Vec<String>,
+    },
+    /// Print every chmod and chown, highlighting world-writable, setuid/setgid and root changes
+    Audit {
+        /// Metadata files to read from
+        files: Vec<String>,
+    },
+    /// Print the applied goals and estimate the replication traffic of goal changes. Goals 1 to 9
+    /// are assumed to keep as many copies as their number, changes involving other goals are not
+    /// estimated.
+    Goals {
+        /// Goal assumed for inodes whose goal was not set before in the analyzed range
+        #[arg(long, value_name = "GOAL")]
+        default_goal: Option<u32>,
+        /// Metadata files to read from
+        files: Vec<String>,
+    },
+    /// Print the files moved to trash, purged and restored, and the data in trash over time
+    Trash {
+        /// Metadata files to read from
+        files: Vec<String>,
+    },
+    /// List the files unlinked and not purged yet, which can still be restored from trash
+    Deleted {
+        /// Only list files at or below this path
+        #[arg(long, value_name = "PATH")]
+        prefix: Option<String>,
+        /// Only list files owned by this user, given as a uid or a name from the passwd file
+        #[arg(long, value_name = "USER")]
+        user: Option<String>,
+        /// passwd file to resolve user names from
+        #[arg(long, value_name = "FILE")]
+        passwd: Option<String>,
+        /// Metadata files to read from
+        files: Vec<String>,
+    },
+    /// Print the rename statistics, atomic saves and the paths of the most renamed inodes
+    Renames {
+        /// Number of atomic saves and renamed inodes to list
+        #[arg(short = 'n', long, default_value_t = 10)]
+        count: usize,
+        /// Metadata files to read from
+        files: Vec<String>,
+    },
+    /// Print the most written extended attribute names and inodes, and every ACL change
+    Xattrs {
+        /// Number of attribute names and inodes to list
+        #[arg(short = 'n', long, default_value_t = 10)]
+        count: usize,
+        /// Metadata files to read from
+        files: Vec<String>,
+    },
+    /// Print every quota change and the usage growth of the fastest growing users and groups
+    Quotas {
+        /// Number of users and groups to list
+        #[arg(short = 'n', long, default_value_t = 10)]
+        count: usize,
+        /// Metadata files to read from
+        files: Vec<String>,
+    },
+    /// Rank directories by namespace operations and by entry churn
+    HotDirs {
+        /// Number of directories to list
+        #[arg(short = 'n', long, default_value_t = 10)]
+        count: usize,
+        /// Metadata files to read from
+        files: Vec<String>,
+    },
+    /// Print the created, deleted and written files grouped by name extension
+    Extensions {
+        /// Number of extensions to list
+        #[arg(short = 'n', long, default_value_t = 10)]
+        count: usize,
+        /// Metadata files to read from
+        files: Vec<String>,
+    },
+    /// Detect windows with unusually many unlinks, rewrites or renames to a common extension
+    Anomalies {
+        /// Length of the windows in which operations are counted, in seconds
+        #[arg(
+            long,
+            value_name = "SECONDS",
+            default_value_t = WINDOW_SECONDS,
+            value_parser = clap::value_parser!(u64).range(1..)
+        )]
+        window: u64,
+        /// Minimum number of operations in a window to flag it
+        #[arg(long, value_name = "COUNT", default_value_t = MIN_EVENTS)]
+        min_events: usize,
+        /// Metadata files to read from
+        files: Vec<String>,
+    },
+    /// List every CREATE, MOVE, ATTR, WRITE and UNLINK touching a path or name, following renames
+    WhoTouched {
+        /// Glob pattern matched against the whole path if it contains a '/', otherwise against
+        /// the name, e.g. '/data/*.csv' or 'report*'
+        pattern: String,
+        /// Metadata files to read from
+        files: Vec<String>,
+    },
+}
+
 fn main() -> std::io::Result<()> {
     let cli = Cli::parse();
     let mut timeline = TimestampRange::default();
@@ -27,7 +184,46 @@ fn main() -> std::io::Result<()> {
     set_timeline_date(cli.start, &mut timeline, true);
     set_timeline_date(cli.stop, &mut timeline, false);
 
-    run(cli.files, timeline)?;
+    let (files, mode) = match cli.report {
+        None => (cli.files, Mode::Summary),
+        Some(Report::Top { count, files }) => (files, Mode::Top(count)),
+        Some(Report::History { inode, files }) => (files, Mode::History(inode)),
+        Some(Report::Locks { count, files }) => (files, Mode::Locks(count)),
+        Some(Report::OpenFiles { count, files }) => (files, Mode::OpenFiles(count)),
+        Some(Report::Sessions { count, files }) => (files, Mode::Sessions(count)),
+        Some(Report::Owners { passwd, files }) => (files, Mode::Owners(passwd)),
+        Some(Report::Audit { files }) => (files, Mode::Audit),
+        Some(Report::Goals {
+            default_goal,
+            files,
+        }) => (files, Mode::Goals(default_goal)),
+        Some(Report::Trash { files }) => (files, Mode::Trash),
+        Some(Report::Deleted {
+            prefix,
+            user,
+            passwd,
+            files,
+        }) => (
+            files,
+            Mode::Deleted {
+                prefix,
+                user,
+                passwd,
+            },
+        ),
+        Some(Report::Renames { count, files }) => (files, Mode::Renames(count)),
+        Some(Report::Xattrs { count, files }) => (files, Mode::Xattrs(count)),
+        Some(Report::Quotas { count, files }) => (files, Mode::Quotas(count)),
+        Some(Report::HotDirs { count, files }) => (files, Mode::HotDirs(count)),
+        Some(Report::Extensions { count, files }) => (files, Mode::Extensions(count)),
+        Some(Report::Anomalies {
+            window,
+            min_events,
+            files,
+        }) => (files, Mode::Anomalies(window, min_events)),
+        Some(Report::WhoTouched { pattern, files }) => (files, Mode::WhoTouched(pattern)),
+    };
+    run(files, timeline, mode)?;
 
     Ok(())
 }
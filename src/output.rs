@@ -1,6 +1,36 @@
+use std::collections::HashMap;
+
 use chrono::TimeDelta;
 
-use crate::{ChangelogResults, TimestampRange};
+use crate::{
+    analysis::{
+        anomalies::{Anomalies, AnomalyKind},
+        deleted::DeletedFiles,
+        extensions::Extensions,
+        goals::GoalImpact,
+        history::{InodeHistory, InodeState},
+        lifetime::{Lifetimes, SHORT_LIVED_SECONDS},
+        locks::LockDurations,
+        open_files::OpenFiles,
+        owners::{OwnerStats, Owners},
+        size_histogram::SizeHistogram,
+        top::TopInodes,
+        who_touched::WhoTouched,
+    },
+    parser::{
+        attributes::{AttributeChange, AttributeChanges},
+        goals::Goals,
+        inodes::Inode,
+        quotas::Quotas,
+        renames::Renames,
+        sessions::Sessions,
+        trash::{Trash, TrashEntry},
+    },
+    ChangelogResults, TimestampRange,
+};
+
+/// Maximum number of rows printed for tables that may grow large
+const MAX_TABLE_ROWS: usize = 10;
 
 /// Print the results of the changelog analysis
 pub fn print_result(
@@ -9,6 +39,13 @@ pub fn print_result(
     results: &ChangelogResults,
 ) {
     let written_amount = results.inodes.all.iter().map(|i| i.written).sum::<u64>();
+    let truncated_amount = results.inodes.all.iter().map(|i| i.truncated).sum::<u64>();
+    let net_size_change = results
+        .inodes
+        .all
+        .iter()
+        .map(|i| i.net_size_change())
+        .sum::<i64>();
     let all_op_count = op_count.iter().map(|op| op.1).sum::<u64>();
     println!("Start: {}", timeline.start);
     println!("End: {}", timeline.end);
@@ -22,6 +59,8 @@ pub fn print_result(
         "Estimated written bytes/s: {}",
         format_bytes(calculate_rate(&written_amount, timeline) as u64)
     );
+    println!("Truncated bytes: {}", format_bytes(truncated_amount));
+    println!("Net size change: {}", format_signed_bytes(net_size_change));
     println!("Total files created: {}", results.file_count);
     println!(
         "Files created/s: {0:.2}",
@@ -37,6 +76,13 @@ pub fn print_result(
         "Inodes created/s: {0:.2}",
         calculate_rate(&results.inode_created_count, timeline)
     );
+    for (node_type, count) in results.node_types.created.iter() {
+        println!("{0:>15}{1:>10}", node_type.label() + ":", count);
+    }
+    println!(
+        "Inodes of unknown type created: {}",
+        results.node_types.unknown()
+    );
     println!("---");
     println!("{0:>15}{1:>10} | Ops/s", "Operation", "Count");
     for v in op_count.iter() {
@@ -47,6 +93,842 @@ pub fn print_result(
             calculate_rate(v.1, timeline)
         );
     }
+    print_chunks(timeline, results);
+    print_snapshots(results);
+    print_size_histogram(&SizeHistogram::new(&results.inodes.all));
+    print_lifetimes(&Lifetimes::new(&results.inodes.all));
+}
+
+/// Print the chunk statistics
+fn print_chunks(timeline: &TimestampRange, results: &ChangelogResults) {
+    let chunks = &results.chunks;
+    println!("---");
+    println!("Chunks written or truncated: {}", chunks.all.len());
+    println!("New chunks allocated: {}", chunks.new_chunks);
+    println!(
+        "New chunks/s: {0:.2}",
+        calculate_rate(&chunks.new_chunks, timeline)
+    );
+    println!("Writes to existing chunks: {}", chunks.reused_chunks);
+    println!(
+        "Next chunk id advanced by: {}",
+        chunks.next_chunk_id_advance()
+    );
+}
+
+/// Print the snapshots and the estimated inodes and data they reference
+fn print_snapshots(results: &ChangelogResults) {
+    let snapshots = &results.snapshots;
+    if snapshots.events.is_empty() {
+        return;
+    }
+    println!("---");
+    println!("Snapshots: {}", snapshots.events.len());
+    println!(
+        "Estimated inodes created by snapshots: {}",
+        snapshots.inodes()
+    );
+    println!(
+        "Estimated bytes referenced by snapshots: {}",
+        format_bytes(snapshots.bytes())
+    );
+    for event in snapshots.events.iter().take(MAX_TABLE_ROWS) {
+        println!(
+            "{} | {} -> {} | {} inodes | {}{}",
+            event.timestamp,
+            event.source_path,
+            event.path,
+            event.inodes,
+            format_bytes(event.bytes),
+            if event.can_overwrite {
+                " | overwrite"
+            } else {
+                ""
+            }
+        );
+    }
+}
+
+/// Print the file size histogram
+fn print_size_histogram(histogram: &SizeHistogram) {
+    println!("---");
+    println!("{0:>15}{1:>10} | Bytes", "File size", "Count");
+    for bucket in histogram.buckets.iter() {
+        println!(
+            "{0:>15}{1:>10} | {2}",
+            bucket.label.to_string() + ":",
+            bucket.count,
+            format_bytes(bucket.bytes)
+        );
+    }
+}
+
+/// Print the file lifetime distribution and the short-lived files grouped by suffix
+fn print_lifetimes(lifetimes: &Lifetimes) {
+    println!("---");
+    println!("{0:>15}{1:>10}", "Lifetime", "Count");
+    for bucket in lifetimes.buckets.iter() {
+        println!(
+            "{0:>15}{1:>10}",
+            bucket.label.to_string() + ":",
+            bucket.count
+        );
+    }
+    println!(
+        "Short-lived files (<{}s): {}",
+        SHORT_LIVED_SECONDS, lifetimes.short_lived
+    );
+    println!(
+        "Short-lived written bytes: {}",
+        format_bytes(lifetimes.short_lived_written)
+    );
+    if lifetimes.suffixes.is_empty() {
+        return;
+    }
+    println!("{0:>15}{1:>10} | Written", "Suffix", "Count");
+    for suffix in lifetimes.suffixes.iter().take(MAX_TABLE_ROWS) {
+        let label = if suffix.suffix.is_empty() {
+            "(none)".to_string()
+        } else {
+            format!(".{}", suffix.suffix)
+        };
+        println!(
+            "{0:>15}{1:>10} | {2}",
+            label + ":",
+            suffix.count,
+            format_bytes(suffix.written)
+        );
+    }
+}
+
+/// Print the inodes with the most written bytes, operations and rewrites
+pub fn print_top(top: &TopInodes) {
+    println!("Top inodes by written bytes:");
+    print_top_list(&top.by_written, |i| format_bytes(i.written));
+    println!("---");
+    println!("Top inodes by operations:");
+    print_top_list(&top.by_operations, |i| i.operations.to_string());
+    println!("---");
+    println!("Top inodes by rewrites:");
+    print_top_list(&top.by_rewrites, |i| i.rewrites.to_string());
+}
+
+/// Print a single list of top inodes with the given value column
+fn print_top_list(inodes: &[&Inode], value: impl Fn(&Inode) -> String) {
+    println!("{0:>15}{1:>6}{2:>15} | Name", "Inode", "Gen", "Value");
+    for i in inodes.iter() {
+        println!(
+            "{0:>15}{1:>6}{2:>15} | {3}",
+            i.inode,
+            i.generation,
+            value(i),
+            i.display_name()
+        );
+    }
+}
+
+/// Print every operation touching an inode, split into generations
+pub fn print_history(history: &InodeHistory) {
+    let generations: Vec<_> = history
+        .generations
+        .iter()
+        .filter(|g| !g.entries.is_empty())
+        .collect();
+    if generations.is_empty() {
+        println!("No operations found for inode {}", history.inode);
+        return;
+    }
+    for (index, generation) in generations.iter().enumerate() {
+        if index > 0 {
+            println!("---");
+        }
+        if generation.generation == 0 {
+            println!(
+                "Inode {} generation 0 (created before the analyzed range):",
+                history.inode
+            );
+        } else {
+            println!(
+                "Inode {} generation {}:",
+                history.inode, generation.generation
+            );
+        }
+        for entry in generation.entries.iter() {
+            let state = format_state(&entry.state);
+            if state.is_empty() {
+                println!("{} | {}", entry.timestamp, entry.operation);
+            } else {
+                println!("{} | {} | {}", entry.timestamp, entry.operation, state);
+            }
+        }
+    }
+}
+
+/// Print the chunk lock hold times, the top offending locks and inodes and the chunks that were
+/// never unlocked. At most `count` chunks never unlocked are listed.
+pub fn print_locks(locks: &LockDurations, results: &ChangelogResults, count: usize) {
+    println!("Released chunk locks: {}", locks.count);
+    for (percentile, duration) in locks.percentiles.iter() {
+        println!("p{}: {}", percentile, format_duration(*duration));
+    }
+    println!("max: {}", format_duration(locks.max));
+    println!("---");
+    println!("Longest chunk locks:");
+    println!(
+        "{0:>20}{1:>15}{2:>6}{3:>12} | Locked at",
+        "Chunk", "Inode", "Gen", "Held"
+    );
+    for hold in locks.longest.iter() {
+        println!(
+            "{0:>20}{1:>15}{2:>6}{3:>12} | {4}",
+            hold.chunk_id,
+            hold.inode,
+            hold.generation,
+            format_duration(hold.duration),
+            hold.locked
+        );
+    }
+    println!("---");
+    println!("Inodes with the longest chunk locks:");
+    println!(
+        "{0:>15}{1:>6}{2:>10}{3:>12}{4:>12} | Name",
+        "Inode", "Gen", "Locks", "Total", "Max"
+    );
+    for inode in locks.inodes.iter() {
+        println!(
+            "{0:>15}{1:>6}{2:>10}{3:>12}{4:>12} | {5}",
+            inode.inode,
+            inode.generation,
+            inode.count,
+            format_duration(inode.total),
+            format_duration(inode.max),
+            inode_name(results, inode.inode, inode.generation)
+        );
+    }
+    println!("---");
+    println!("Chunks never unlocked: {}", locks.never_unlocked.len());
+    for chunk in locks.never_unlocked.iter().take(count) {
+        if let Some(locked) = chunk.locked_since {
+            println!(
+                "{0:>20}{1:>15}{2:>6} | Locked at {3}",
+                chunk.chunk_id, chunk.inode, chunk.generation, locked
+            );
+        }
+    }
+}
+
+/// Print the concurrently open files over time, the open durations and the files still open at
+/// the end of the analyzed range. At most `count` still open files are listed.
+pub fn print_open_files(open_files: &OpenFiles, results: &ChangelogResults, count: usize) {
+    let sessions = &results.sessions;
+    match sessions.peak_time {
+        Some(time) => println!("Peak open files: {} at {}", sessions.peak_open, time),
+        None => println!("Peak open files: 0"),
+    }
+    println!("{0:>20}{1:>10}", "Hour", "Max open");
+    for (hour, open) in sessions.open_per_hour.iter() {
+        println!("{0:>20}{1:>10}", hour.format("%Y-%m-%d %H:00"), open);
+    }
+    println!("---");
+    println!(
+        "Closed files with known open duration: {}",
+        open_files.count
+    );
+    for (percentile, duration) in open_files.percentiles.iter() {
+        println!("p{}: {}", percentile, format_duration(*duration));
+    }
+    println!("max: {}", format_duration(open_files.max));
+    println!("Longest open files:");
+    println!(
+        "{0:>15}{1:>6}{2:>10}{3:>12} | Name",
+        "Inode", "Gen", "Session", "Open"
+    );
+    for handle in open_files.longest.iter() {
+        println!(
+            "{0:>15}{1:>6}{2:>10}{3:>12} | {4}",
+            handle.inode,
+            handle.generation,
+            handle.session,
+            handle.duration().map_or("?".to_string(), format_duration),
+            inode_name(results, handle.inode, handle.generation)
+        );
+    }
+    println!("---");
+    println!(
+        "Files still open at the end: {}",
+        open_files.still_open.len()
+    );
+    if open_files.still_open.is_empty() {
+        return;
+    }
+    println!("{0:>10}{1:>10}", "Session", "Open");
+    for session in open_files.sessions.iter() {
+        println!("{0:>10}{1:>10}", session.session, session.count);
+    }
+    println!(
+        "{0:>15}{1:>6}{2:>10}{3:>20} | Name",
+        "Inode", "Gen", "Session", "Opened"
+    );
+    for handle in open_files.still_open.iter().take(count) {
+        println!(
+            "{0:>15}{1:>6}{2:>10}{3:>20} | {4}",
+            handle.inode,
+            handle.generation,
+            handle.session,
+            handle.opened.map_or("?".to_string(), |t| t.to_string()),
+            inode_name(results, handle.inode, handle.generation)
+        );
+    }
+}
+
+/// Print the activity of the `count` most active sessions
+pub fn print_sessions(sessions: &Sessions, count: usize) {
+    println!("Sessions seen: {}", sessions.all.len());
+    println!(
+        "Written bytes without an open session: {}",
+        format_bytes(sessions.unattributed_written)
+    );
+    println!(
+        "{0:>10}{1:>20}{2:>12}{3:>10}{4:>10}{5:>15}",
+        "Session", "Created", "Active", "Opened", "Written", "Written bytes"
+    );
+    for session in sessions.by_activity().iter().take(count) {
+        println!(
+            "{0:>10}{1:>20}{2:>12}{3:>10}{4:>10}{5:>15}",
+            session.session,
+            session.created.map_or("?".to_string(), |t| t.to_string()),
+            session.lifetime().map_or("?".to_string(), format_duration),
+            session.files_opened,
+            session.written_inodes.len(),
+            format_bytes(session.written)
+        );
+    }
+}
+
+/// Print the statistics per user and group. Uids found in `users` are printed as names.
+pub fn print_owners(owners: &Owners, users: &HashMap<u32, String>) {
+    println!("Users:");
+    print_owner_stats(&owners.users, "User", |uid| {
+        users.get(&uid).cloned().unwrap_or_else(|| uid.to_string())
+    });
+    println!("---");
+    println!("Groups:");
+    print_owner_stats(&owners.groups, "Group", |gid| gid.to_string());
+    println!("---");
+    println!("Inodes with unknown owner: {}", owners.unknown_owner);
+    println!(
+        "Written bytes with unknown owner: {}",
+        format_bytes(owners.unknown_owner_written)
+    );
+}
+
+/// Print a single table of owner statistics, with owner ids formatted by `name`
+fn print_owner_stats(stats: &[OwnerStats], header: &str, name: impl Fn(u32) -> String) {
+    println!(
+        "{0:>15}{1:>10}{2:>10}{3:>10} | Written",
+        header, "Files", "Dirs", "Deleted"
+    );
+    for owner in stats.iter() {
+        println!(
+            "{0:>15}{1:>10}{2:>10}{3:>10} | {4}",
+            name(owner.id),
+            owner.files_created,
+            owner.dirs_created,
+            owner.deleted,
+            format_bytes(owner.written)
+        );
+    }
+}
+
+/// Print every permission and ownership change, marking security relevant ones with '!'
+pub fn print_audit(attribute_changes: &AttributeChanges) {
+    let changes = &attribute_changes.changes;
+    let flagged = changes.iter().filter(|c| !c.warnings().is_empty()).count();
+    println!(
+        "Permission changes: {}",
+        changes.iter().filter(|c| c.is_chmod()).count()
+    );
+    println!(
+        "Ownership changes: {}",
+        changes.iter().filter(|c| c.is_chown()).count()
+    );
+    println!("Security relevant changes: {}", flagged);
+    println!("---");
+    for change in changes.iter() {
+        println!("{}", format_attribute_change(change));
+    }
+}
+
+/// Format a single permission or ownership change
+fn format_attribute_change(change: &AttributeChange) -> String {
+    let warnings = change.warnings();
+    let mut fields = vec![
+        format!(
+            "{} {}",
+            if warnings.is_empty() { " " } else { "!" },
+            change.timestamp
+        ),
+        format!("{} ({})", change.path, change.inode),
+    ];
+    if change.is_chmod() {
+        fields.push(format!(
+            "chmod {} -> {:o}",
+            change
+                .old
+                .map_or("?".to_string(), |old| format!("{:o}", old.mode)),
+            change.new.mode
+        ));
+    }
+    if change.is_chown() {
+        fields.push(format!(
+            "chown {} -> {}:{}",
+            change
+                .old
+                .map_or("?".to_string(), |old| format!("{}:{}", old.uid, old.gid)),
+            change.new.uid,
+            change.new.gid
+        ));
+    }
+    if !warnings.is_empty() {
+        fields.push(warnings.join(", "));
+    }
+    fields.join(" | ")
+}
+
+/// Print the applied goals, the recursive goal changes and the estimated replication traffic
+pub fn print_goals(goals: &Goals, impact: &GoalImpact, results: &ChangelogResults) {
+    println!("{0:>10}{1:>12}{2:>12}", "Goal", "Operations", "Changed");
+    for (goal, stats) in goals.applied.iter() {
+        println!(
+            "{0:>10}{1:>12}{2:>12}",
+            goal, stats.operations, stats.changed
+        );
+    }
+    println!("---");
+    println!("Goal changes on single inodes: {}", goals.changes.len());
+    println!("Estimated changes: {}", impact.estimated);
+    println!("Changes with unknown size or goal: {}", impact.unknown);
+    println!(
+        "Changes not estimated, involving goals other than 1 to 9 copies: {}",
+        impact.not_estimated
+    );
+    println!(
+        "Estimated bytes to replicate: {}",
+        format_bytes(impact.replicated_bytes)
+    );
+    println!(
+        "Estimated bytes to remove: {}",
+        format_bytes(impact.removed_bytes)
+    );
+    println!("---");
+    println!("Recursive goal changes: {}", goals.recursive.len());
+    for change in goals.recursive.iter() {
+        println!(
+            "{} | {} | goal {} | {} inodes changed, {} known inodes below",
+            change.timestamp,
+            results.namespace.path(change.inode),
+            change.goal,
+            change.changed,
+            change.descendants
+        );
+    }
+}
+
+/// Print the files moved to trash, purged and restored, and the data in trash over time
+pub fn print_trash(trash: &Trash) {
+    let in_trash: Vec<&TrashEntry> = trash.still_in_trash().collect();
+    let purged: Vec<&TrashEntry> = trash
+        .entries
+        .iter()
+        .filter(|e| e.purged.is_some())
+        .collect();
+    let undeleted: Vec<&TrashEntry> = trash
+        .entries
+        .iter()
+        .filter(|e| e.undeleted.is_some())
+        .collect();
+    println!(
+        "Files moved to trash: {} ({})",
+        trash.entries.len(),
+        format_bytes(trash_bytes(trash.entries.iter()))
+    );
+    println!(
+        "Files removed immediately (trash time 0): {}",
+        trash.removed_immediately
+    );
+    println!(
+        "Purged from trash: {} ({})",
+        purged.len(),
+        format_bytes(trash_bytes(purged.into_iter()))
+    );
+    println!(
+        "Restored from trash: {} ({})",
+        undeleted.len(),
+        format_bytes(trash_bytes(undeleted.into_iter()))
+    );
+    println!(
+        "Purged or restored, unlinked before the analyzed range: {} / {}",
+        trash.unknown_purges, trash.unknown_undeletes
+    );
+    println!(
+        "Still in trash at the end: {} ({})",
+        in_trash.len(),
+        format_bytes(trash.bytes)
+    );
+    println!(
+        "Still in trash with unknown size: {}",
+        in_trash.iter().filter(|e| e.size.is_none()).count()
+    );
+    println!(
+        "Still in trash of unknown type, possibly directories: {}",
+        in_trash.iter().filter(|e| !e.known_file).count()
+    );
+    match in_trash.iter().filter_map(|e| e.expires()).max() {
+        Some(expires) => println!("Latest known trash time expiry: {}", expires),
+        None => println!("Latest known trash time expiry: unknown"),
+    }
+    match trash.peak_time {
+        Some(time) => println!(
+            "Peak data in trash: {} at {}",
+            format_bytes(trash.peak_bytes),
+            time
+        ),
+        None => println!("Peak data in trash: 0"),
+    }
+    println!("---");
+    println!("{0:>15}{1:>12}", "Trash time", "Operations");
+    for (trash_time, operations) in trash.trash_times_applied.iter() {
+        println!("{0:>15}{1:>12}", trash_time.to_string() + "s", operations);
+    }
+    println!("---");
+    println!("{0:>20}{1:>15}", "Hour", "Max in trash");
+    for (hour, bytes) in trash.bytes_per_hour.iter() {
+        println!(
+            "{0:>20}{1:>15}",
+            hour.format("%Y-%m-%d %H:00"),
+            format_bytes(*bytes)
+        );
+    }
+}
+
+/// Print the files that can still be restored from trash, with user names from `users`.
+/// Entries not known to be files are marked with the type '?'.
+pub fn print_deleted(deleted: &DeletedFiles, users: &HashMap<u32, String>) {
+    println!(
+        "Restorable files: {} ({}, {} of unknown size, {} of unknown type)",
+        deleted.files.len(),
+        format_bytes(deleted.bytes),
+        deleted.unknown_size,
+        deleted.unknown_type
+    );
+    if deleted.files.is_empty() {
+        return;
+    }
+    println!(
+        "{0:>20}{1:>6}{2:>12}{3:>12}{4:>20} | Path",
+        "Deleted", "Type", "Size", "Owner", "Expires"
+    );
+    for entry in deleted.files.iter() {
+        let owner = entry.uid.map_or("?".to_string(), |uid| {
+            users.get(&uid).cloned().unwrap_or(uid.to_string())
+        });
+        println!(
+            "{0:>20}{1:>6}{2:>12}{3:>12}{4:>20} | {5}",
+            entry.deleted,
+            if entry.known_file { "f" } else { "?" },
+            entry.size.map_or("?".to_string(), format_bytes),
+            owner,
+            entry.expires().map_or("?".to_string(), |t| t.to_string()),
+            entry.path
+        );
+    }
+}
+
+/// Print every operation touching entries matching the pattern
+pub fn print_who_touched(who_touched: &WhoTouched) {
+    println!(
+        "Operations touching {}: {}",
+        who_touched.pattern,
+        who_touched.events.len()
+    );
+    if who_touched.events.is_empty() {
+        return;
+    }
+    println!(
+        "{0:>20}{1:>10}{2:>15}{3:>10} | Path",
+        "Time", "Operation", "Inode", "Session"
+    );
+    for event in who_touched.events.iter() {
+        println!(
+            "{0:>20}{1:>10}{2:>15}{3:>10} | {4}",
+            event.timestamp,
+            event.operation,
+            event.inode,
+            event.session.map_or("?".to_string(), |s| s.to_string()),
+            event.path
+        );
+    }
+}
+
+/// Print the rename statistics and at most `count` atomic saves and most renamed inodes
+pub fn print_renames(renames: &Renames, count: usize) {
+    println!("Moves: {}", renames.moves);
+    println!("Moves into another directory: {}", renames.cross_directory);
+    println!("Moves replacing an existing entry: {}", renames.replacing);
+    println!("Atomic saves: {}", renames.atomic_saves.len());
+    for save in renames.atomic_saves.iter().take(count) {
+        println!(
+            "{} | inode {} written as {}, renamed to {} (replaced inode {})",
+            save.timestamp, save.inode, save.temporary_path, save.path, save.replaced
+        );
+    }
+    println!("---");
+    println!("Most renamed inodes:");
+    for (inode, paths) in renames.most_renamed(count) {
+        println!("{0:>15} | {1}", inode, paths.join(" -> "));
+    }
+}
+
+/// Print the `count` most written extended attribute names and inodes, and every ACL change
+pub fn print_xattrs(results: &ChangelogResults, count: usize) {
+    let xattrs = &results.xattrs;
+    println!("Extended attribute names: {}", xattrs.names.len());
+    println!(
+        "{0:>30}{1:>10}{2:>10}{3:>12}{4:>10} | Top inode",
+        "Name", "Sets", "Removes", "Value bytes", "Inodes"
+    );
+    for (name, stats) in xattrs.top_names(count) {
+        let top_inode = stats.top_inode().map_or(String::new(), |(inode, ops)| {
+            format!("{} ({} ops)", results.namespace.path(inode), ops)
+        });
+        println!(
+            "{0:>30}{1:>10}{2:>10}{3:>12}{4:>10} | {5}",
+            name,
+            stats.sets,
+            stats.removes,
+            format_bytes(stats.value_bytes),
+            stats.inodes.len(),
+            top_inode
+        );
+    }
+    println!("---");
+    println!("{0:>15}{1:>10} | Path", "Inode", "Ops");
+    for (inode, ops) in xattrs.top_inodes(count) {
+        println!(
+            "{0:>15}{1:>10} | {2}",
+            inode,
+            ops,
+            results.namespace.path(inode)
+        );
+    }
+    println!("---");
+    println!("ACL changes: {}", xattrs.acl_changes.len());
+    for change in xattrs.acl_changes.iter() {
+        println!(
+            "{} | {} ({}) | {} | {}",
+            change.timestamp,
+            change.path,
+            change.inode,
+            change.acl_type,
+            change.acl.as_deref().unwrap_or("<deleted>")
+        );
+    }
+}
+
+/// Print every quota change and the usage growth of the `count` fastest growing users and
+/// groups, with their latest known size limit. Comparing the growth per day with the current
+/// usage shows how soon a quota will be exhausted.
+pub fn print_quotas(quotas: &Quotas, owners: &Owners, timeline: &TimestampRange, count: usize) {
+    println!("Quota changes: {}", quotas.changes.len());
+    for change in quotas.changes.iter() {
+        let quota = &change.quota;
+        println!(
+            "{} | {} {} | {} {} limit | {} -> {}",
+            change.timestamp,
+            if quota.group { "group" } else { "user" },
+            quota.owner,
+            if quota.hard { "hard" } else { "soft" },
+            if quota.inodes { "inode" } else { "size" },
+            change
+                .old
+                .map_or("?".to_string(), |old| format_limit(old, quota.inodes)),
+            format_limit(quota.limit, quota.inodes)
+        );
+    }
+    let days = (timeline.end - timeline.start).num_seconds() as f64 / 86400.0;
+    for (stats, header, group) in [
+        (&owners.users, "User", false),
+        (&owners.groups, "Group", true),
+    ] {
+        let mut growing: Vec<&OwnerStats> = stats.iter().collect();
+        growing.sort_by(|a, b| b.byte_growth.cmp(&a.byte_growth).then(a.id.cmp(&b.id)));
+        println!("---");
+        println!(
+            "{0:>15}{1:>10}{2:>15}{3:>15} | Size limit",
+            header, "Inodes", "Bytes", "Bytes/day"
+        );
+        for owner in growing.into_iter().take(count) {
+            let per_day = if days > 0.0 {
+                (owner.byte_growth as f64 / days) as i64
+            } else {
+                owner.byte_growth
+            };
+            println!(
+                "{0:>15}{1:>10}{2:>15}{3:>15} | {4}",
+                owner.id,
+                owner.inode_growth,
+                format_signed_bytes(owner.byte_growth),
+                format_signed_bytes(per_day),
+                quotas
+                    .size_limit(group, owner.id)
+                    .map_or("?".to_string(), format_bytes)
+            );
+        }
+    }
+}
+
+/// Format a quota limit, 0 meaning no limit
+fn format_limit(limit: u64, inodes: bool) -> String {
+    match (limit, inodes) {
+        (0, _) => "none".to_string(),
+        (limit, true) => limit.to_string(),
+        (limit, false) => format_bytes(limit),
+    }
+}
+
+/// Print the `count` directories with the most namespace operations and the highest churn
+pub fn print_hot_dirs(results: &ChangelogResults, count: usize) {
+    let directories = &results.directories;
+    println!(
+        "Directories with namespace operations: {}",
+        directories.all.len()
+    );
+    for (title, hot) in [
+        (
+            "Most namespace operations:",
+            directories.by_operations(count),
+        ),
+        ("Highest entry churn:", directories.by_churn(count)),
+    ] {
+        println!("---");
+        println!("{}", title);
+        println!(
+            "{0:>15}{1:>12}{2:>10}{3:>10}{4:>10}{5:>10} | Path",
+            "Inode", "Operations", "Added", "Removed", "Churn", "Net"
+        );
+        for directory in hot {
+            println!(
+                "{0:>15}{1:>12}{2:>10}{3:>10}{4:>10}{5:>10} | {6}",
+                directory.inode,
+                directory.operations,
+                directory.added,
+                directory.removed,
+                directory.churn(),
+                directory.net_entries(),
+                results.namespace.path(directory.inode)
+            );
+        }
+    }
+}
+
+/// Print the file statistics of the `count` extensions with the most created files
+pub fn print_extensions(extensions: &Extensions, count: usize) {
+    println!(
+        "{0:>15}{1:>10}{2:>10}{3:>15}{4:>15}",
+        "Extension", "Created", "Deleted", "Written", "Average size"
+    );
+    for stats in extensions.extensions.iter().take(count) {
+        let extension = match stats.extension.as_str() {
+            "" => "<none>".to_string(),
+            extension => format!(".{}", extension),
+        };
+        println!(
+            "{0:>15}{1:>10}{2:>10}{3:>15}{4:>15}",
+            extension,
+            stats.created,
+            stats.deleted,
+            format_bytes(stats.written),
+            format_bytes(stats.average_size())
+        );
+    }
+}
+
+/// Print the detected mass operations with a sample of the affected inodes
+pub fn print_anomalies(anomalies: &Anomalies) {
+    println!("Suspicious mass operations: {}", anomalies.anomalies.len());
+    for anomaly in anomalies.anomalies.iter() {
+        let kind = match &anomaly.kind {
+            AnomalyKind::MassDeletion => "mass deletion".to_string(),
+            AnomalyKind::MassRewrite => "mass rewrite".to_string(),
+            AnomalyKind::MassRename(extension) => format!("mass rename to .{}", extension),
+        };
+        let sample: Vec<String> = anomaly
+            .inodes
+            .iter()
+            .take(MAX_TABLE_ROWS)
+            .map(|i| i.to_string())
+            .collect();
+        let more = if anomaly.inodes.len() > MAX_TABLE_ROWS {
+            ", ..."
+        } else {
+            ""
+        };
+        println!(
+            "{} - {} | {} | {} inodes | {} | inodes: {}{}",
+            anomaly.start,
+            anomaly.end,
+            kind,
+            anomaly.count,
+            anomaly.subtree,
+            sample.join(", "),
+            more
+        );
+    }
+}
+
+/// Sum the known sizes of trash entries
+fn trash_bytes<'a>(entries: impl Iterator<Item = &'a TrashEntry>) -> u64 {
+    entries.filter_map(|e| e.size).sum()
+}
+
+/// Return the name of an inode generation, or a placeholder if it is not known
+fn inode_name(results: &ChangelogResults, inode: u64, generation: u64) -> &str {
+    results
+        .inodes
+        .find(inode, generation)
+        .map_or("<unknown>", |i| i.display_name())
+}
+
+/// Format a duration in seconds
+fn format_duration(duration: TimeDelta) -> String {
+    format!("{}s", duration.num_seconds())
+}
+
+/// Format the known state of an inode, leaving out unknown fields
+fn format_state(state: &InodeState) -> String {
+    let mut fields = Vec::new();
+    if let Some(name) = &state.name {
+        fields.push(format!("name: {}", name));
+    }
+    if let Some(size) = state.size {
+        fields.push(format!("size: {}", size));
+    }
+    if let Some(mode) = state.mode {
+        fields.push(format!("mode: {:o}", mode));
+    }
+    if let (Some(uid), Some(gid)) = (state.uid, state.gid) {
+        fields.push(format!("owner: {}:{}", uid, gid));
+    }
+    if let Some(goal) = state.goal {
+        fields.push(format!("goal: {}", goal));
+    }
+    if let Some(links) = state.links {
+        fields.push(format!("links: {}", links));
+    }
+    if let Some(target) = &state.symlink_target {
+        fields.push(format!("target: {}", target));
+    }
+    fields.join(", ")
 }
 
 /// Calculate the rate of operations per second
@@ -74,3 +956,9 @@ fn format_bytes(bytes: u64) -> String {
 
     format!("{:.2} {}", size, units[unit_index])
 }
+
+/// Format a signed byte amount into a human-readable string with an explicit sign
+fn format_signed_bytes(bytes: i64) -> String {
+    let sign = if bytes < 0 { "-" } else { "+" };
+    format!("{}{}", sign, format_bytes(bytes.unsigned_abs()))
+}
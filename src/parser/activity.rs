@@ -0,0 +1,72 @@
+use std::collections::HashSet;
+
+use chrono::NaiveDateTime;
+
+/// A single operation on an inode, recorded for the detection of mass operations
+#[derive(Debug, Clone, Copy)]
+pub struct ActivityEvent {
+    /// The timestamp of the operation
+    pub timestamp: NaiveDateTime,
+    /// The inode the operation was applied to
+    pub inode: u64,
+    /// The generation of the inode
+    pub generation: u64,
+    /// The parent directory of the inode, if known
+    pub parent: Option<u64>,
+}
+
+/// A rename changing the extension of a name
+#[derive(Debug)]
+pub struct ExtensionChange {
+    /// The rename
+    pub event: ActivityEvent,
+    /// The extension of the new name
+    pub extension: String,
+}
+
+/// Struct to record the operations that are suspicious in large numbers: unlinks, rewrites of
+/// files, i.e. the length dropping to 0 and growing again, and renames changing the extension
+#[derive(Debug, Default)]
+pub struct Activity {
+    /// All UNLINK operations
+    pub unlinks: Vec<ActivityEvent>,
+    /// All rewrites, recorded when the length grows again
+    pub rewrites: Vec<ActivityEvent>,
+    /// All renames changing the extension
+    pub extension_changes: Vec<ExtensionChange>,
+    /// HashSet to hold the inodes whose length dropped to 0
+    zeroed: HashSet<u64>,
+}
+
+impl Activity {
+    /// Create a new Activity struct without any events
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record an UNLINK operation
+    pub fn unlink(&mut self, event: ActivityEvent) {
+        self.zeroed.remove(&event.inode);
+        self.unlinks.push(event);
+    }
+
+    /// Record a LENGTH operation. Unlike `Inodes::update_length`, this also counts rewrites of
+    /// files created before the analyzed range, whose previous length is not known.
+    pub fn length(&mut self, event: ActivityEvent, length: u64) {
+        if length == 0 {
+            self.zeroed.insert(event.inode);
+        } else if self.zeroed.remove(&event.inode) {
+            self.rewrites.push(event);
+        }
+    }
+
+    /// Record a rename, if it changes the extension of the name
+    pub fn rename(&mut self, event: ActivityEvent, old: Option<&str>, new: Option<&str>) {
+        if let Some(extension) = new.filter(|new| old != Some(*new)) {
+            self.extension_changes.push(ExtensionChange {
+                event,
+                extension: extension.to_string(),
+            });
+        }
+    }
+}
@@ -0,0 +1,118 @@
+use std::collections::HashMap;
+
+use chrono::NaiveDateTime;
+
+/// Permission bits of the setuid flag
+const SETUID: u32 = 0o4000;
+/// Permission bits of the setgid flag
+const SETGID: u32 = 0o2000;
+/// Permission bits of the write flag for others
+const WORLD_WRITABLE: u32 = 0o002;
+
+/// The permission bits and ownership of an inode
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Attributes {
+    /// The permission bits
+    pub mode: u32,
+    /// The owner uid
+    pub uid: u32,
+    /// The owner gid
+    pub gid: u32,
+}
+
+/// Struct to track the attributes of every inode seen in CREATE and ATTR operations, and to
+/// record every change of permissions or ownership
+#[derive(Debug, Default)]
+pub struct AttributeChanges {
+    /// All changes of permissions or ownership, in the order they happened
+    pub changes: Vec<AttributeChange>,
+    /// HashMap to hold the latest known attributes of each inode
+    known: HashMap<u64, Attributes>,
+}
+
+impl AttributeChanges {
+    /// Create a new AttributeChanges struct without any known attributes
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the attributes of a newly created inode, without recording a change
+    pub fn create(&mut self, inode: u64, attributes: Attributes) {
+        self.known.insert(inode, attributes);
+    }
+
+    /// Update the attributes of an inode from an ATTR operation, recording a change if the
+    /// permissions or ownership differ from the previously known ones. The first ATTR of an
+    /// inode not created in the analyzed range is always recorded, with unknown old attributes.
+    pub fn update(
+        &mut self,
+        inode: u64,
+        attributes: Attributes,
+        path: impl FnOnce() -> String,
+        timestamp: NaiveDateTime,
+    ) {
+        let previous = self.known.insert(inode, attributes);
+        if previous != Some(attributes) {
+            self.changes.push(AttributeChange {
+                timestamp,
+                inode,
+                path: path(),
+                old: previous,
+                new: attributes,
+            });
+        }
+    }
+}
+
+/// A change of permissions or ownership of an inode
+#[derive(Debug)]
+pub struct AttributeChange {
+    /// The timestamp of the change
+    pub timestamp: NaiveDateTime,
+    /// The inode number
+    pub inode: u64,
+    /// The path of the inode at the time of the change, as far as it is known
+    pub path: String,
+    /// The attributes before the change, if known
+    pub old: Option<Attributes>,
+    /// The attributes after the change
+    pub new: Attributes,
+}
+
+impl AttributeChange {
+    /// Whether the permission bits changed. Always true if the old attributes are not known.
+    pub fn is_chmod(&self) -> bool {
+        self.old.is_none_or(|old| old.mode != self.new.mode)
+    }
+
+    /// Whether the owner uid or gid changed. Always true if the old attributes are not known.
+    pub fn is_chown(&self) -> bool {
+        self.old
+            .is_none_or(|old| old.uid != self.new.uid || old.gid != self.new.gid)
+    }
+
+    /// Security relevant aspects of the change: newly world-writable, setuid or setgid modes and
+    /// ownership moving to root. If the old attributes are not known, the new ones alone decide.
+    pub fn warnings(&self) -> Vec<&'static str> {
+        let added = |bits: u32| {
+            self.new.mode & bits != 0 && self.old.is_none_or(|old| old.mode & bits == 0)
+        };
+        let mut warnings = Vec::new();
+        if added(WORLD_WRITABLE) {
+            warnings.push("world-writable");
+        }
+        if added(SETUID) {
+            warnings.push("setuid");
+        }
+        if added(SETGID) {
+            warnings.push("setgid");
+        }
+        if self.new.uid == 0 && self.old.is_none_or(|old| old.uid != 0) {
+            warnings.push("owner root");
+        }
+        if self.new.gid == 0 && self.old.is_none_or(|old| old.gid != 0) {
+            warnings.push("group root");
+        }
+        warnings
+    }
+}
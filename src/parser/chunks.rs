@@ -0,0 +1,148 @@
+use std::collections::HashMap;
+
+use chrono::{NaiveDateTime, TimeDelta};
+
+use super::line_parser::{Trunc, Write};
+
+/// Struct to manage all chunks seen in WRITE and TRUNC operations
+#[derive(Debug, Default)]
+pub struct Chunks {
+    /// HashMap to hold all chunks by their chunk id
+    pub all: HashMap<u64, Chunk>,
+    /// The number of writes that allocated a new chunk
+    pub new_chunks: u64,
+    /// The number of writes to an already existing chunk
+    pub reused_chunks: u64,
+    /// The first NEXTCHUNKID value seen, if any
+    pub first_next_chunk_id: Option<u64>,
+    /// The last NEXTCHUNKID value seen, if any
+    pub last_next_chunk_id: Option<u64>,
+    /// All chunk locks taken by WRITE or TRUNC and released by UNLOCK, in order of release
+    pub lock_holds: Vec<LockHold>,
+}
+
+impl Chunks {
+    /// Create a new Chunks struct with an empty chunk table
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a WRITE operation on a chunk of the given inode generation, locking the chunk
+    pub fn write(&mut self, write: &Write, generation: u64, timestamp: NaiveDateTime) {
+        if write.new_chunk {
+            self.new_chunks += 1;
+        } else {
+            self.reused_chunks += 1;
+        }
+        let chunk = self.get_or_insert(write.chunk_id, write.inode, generation, write.index);
+        chunk.writes += 1;
+        chunk.allocated |= write.new_chunk;
+        chunk.locked_since.get_or_insert(timestamp);
+    }
+
+    /// Record a TRUNC operation on a chunk of the given inode generation, locking the chunk
+    pub fn truncate(&mut self, trunc: &Trunc, generation: u64, timestamp: NaiveDateTime) {
+        let chunk = self.get_or_insert(trunc.chunk_id, trunc.inode, generation, trunc.index);
+        chunk.truncations += 1;
+        chunk.locked_since.get_or_insert(timestamp);
+    }
+
+    /// Record an UNLOCK operation releasing the lock on a chunk. Unknown chunks are ignored.
+    pub fn unlock(&mut self, chunk_id: u64, timestamp: NaiveDateTime) {
+        if let Some(chunk) = self.all.get_mut(&chunk_id) {
+            chunk.unlocks += 1;
+            if let Some(locked) = chunk.locked_since.take() {
+                self.lock_holds.push(LockHold {
+                    chunk_id,
+                    inode: chunk.inode,
+                    generation: chunk.generation,
+                    locked,
+                    duration: timestamp - locked,
+                });
+            }
+        }
+    }
+
+    /// Return all chunks that are still locked, i.e. were not unlocked in the analyzed range,
+    /// ordered by the time they were locked
+    pub fn still_locked(&self) -> Vec<&Chunk> {
+        let mut chunks: Vec<&Chunk> = self
+            .all
+            .values()
+            .filter(|c| c.locked_since.is_some())
+            .collect();
+        chunks.sort_by_key(|c| (c.locked_since, c.chunk_id));
+        chunks
+    }
+
+    /// Record a NEXTCHUNKID operation
+    pub fn update_next_chunk_id(&mut self, next_chunk_id: u64) {
+        self.first_next_chunk_id.get_or_insert(next_chunk_id);
+        self.last_next_chunk_id = Some(next_chunk_id);
+    }
+
+    /// How much the next chunk id advanced between the first and the last NEXTCHUNKID operation
+    pub fn next_chunk_id_advance(&self) -> u64 {
+        match (self.first_next_chunk_id, self.last_next_chunk_id) {
+            (Some(first), Some(last)) => last.saturating_sub(first),
+            _ => 0,
+        }
+    }
+
+    /// Get a chunk from the table, inserting it if it is not known yet. The chunk always belongs
+    /// to the inode of the latest operation on it.
+    fn get_or_insert(
+        &mut self,
+        chunk_id: u64,
+        inode: u64,
+        generation: u64,
+        index: u64,
+    ) -> &mut Chunk {
+        let chunk = self.all.entry(chunk_id).or_insert(Chunk {
+            chunk_id,
+            ..Default::default()
+        });
+        chunk.inode = inode;
+        chunk.generation = generation;
+        chunk.index = index;
+        chunk
+    }
+}
+
+/// Struct to hold chunk information
+#[derive(Debug, Default)]
+pub struct Chunk {
+    /// The chunk id
+    pub chunk_id: u64,
+    /// The inode number the chunk belongs to
+    pub inode: u64,
+    /// The generation of the inode the chunk belongs to
+    pub generation: u64,
+    /// The index of the chunk in the file
+    pub index: u64,
+    /// Whether the chunk was allocated in the analyzed range
+    pub allocated: bool,
+    /// The number of WRITE operations on the chunk
+    pub writes: u64,
+    /// The number of TRUNC operations on the chunk
+    pub truncations: u64,
+    /// The number of UNLOCK operations on the chunk
+    pub unlocks: u64,
+    /// The timestamp the chunk was locked, if it is currently locked
+    pub locked_since: Option<NaiveDateTime>,
+}
+
+/// A chunk lock taken by a WRITE or TRUNC operation and released by an UNLOCK operation
+#[derive(Debug)]
+pub struct LockHold {
+    /// The locked chunk id
+    pub chunk_id: u64,
+    /// The inode number the chunk belongs to
+    pub inode: u64,
+    /// The generation of the inode the chunk belongs to
+    pub generation: u64,
+    /// The timestamp the chunk was locked
+    pub locked: NaiveDateTime,
+    /// How long the lock was held
+    pub duration: TimeDelta,
+}
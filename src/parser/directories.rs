@@ -0,0 +1,98 @@
+use std::collections::HashMap;
+
+/// Statistics of the namespace operations in a single directory
+#[derive(Debug, Default)]
+pub struct DirectoryStats {
+    /// The directory inode number
+    pub inode: u64,
+    /// The number of namespace operations in the directory. A MOVE between two directories is
+    /// counted in both.
+    pub operations: u64,
+    /// The number of entries added by CREATE, SYMLINK, LINK and MOVE
+    pub added: u64,
+    /// The number of entries removed by UNLINK and MOVE, including entries replaced by a MOVE
+    pub removed: u64,
+}
+
+impl DirectoryStats {
+    /// The entry churn, i.e. the number of entries added and removed again. A directory with
+    /// many create/unlink cycles has a high churn but a stable number of entries.
+    pub fn churn(&self) -> u64 {
+        self.added.min(self.removed)
+    }
+
+    /// The net change of the number of entries
+    pub fn net_entries(&self) -> i64 {
+        self.added as i64 - self.removed as i64
+    }
+}
+
+/// Struct to count the namespace operations by parent directory
+#[derive(Debug, Default)]
+pub struct Directories {
+    /// HashMap to hold the statistics of every directory with namespace operations
+    pub all: HashMap<u64, DirectoryStats>,
+}
+
+impl Directories {
+    /// Create a new Directories struct without any operations
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record an operation adding an entry to a directory
+    pub fn add(&mut self, parent: u64) {
+        let stats = self.stats_mut(parent);
+        stats.operations += 1;
+        stats.added += 1;
+    }
+
+    /// Record an operation removing an entry from a directory
+    pub fn remove(&mut self, parent: u64) {
+        let stats = self.stats_mut(parent);
+        stats.operations += 1;
+        stats.removed += 1;
+    }
+
+    /// Record a MOVE operation. A rename within a directory is a single operation without
+    /// changing its entries, apart from a replaced entry.
+    pub fn rename(&mut self, src_parent: u64, dst_parent: u64, replaced: bool) {
+        if src_parent != dst_parent {
+            self.remove(src_parent);
+            self.add(dst_parent);
+        } else {
+            self.stats_mut(dst_parent).operations += 1;
+        }
+        if replaced {
+            self.stats_mut(dst_parent).removed += 1;
+        }
+    }
+
+    /// The directories with the most namespace operations, at most `count`
+    pub fn by_operations(&self, count: usize) -> Vec<&DirectoryStats> {
+        self.top_by(count, |d| d.operations)
+    }
+
+    /// The directories with the highest entry churn, at most `count`
+    pub fn by_churn(&self, count: usize) -> Vec<&DirectoryStats> {
+        self.top_by(count, |d| d.churn())
+    }
+
+    /// The directories with the highest value, at most `count`, ignoring directories with a
+    /// value of 0
+    fn top_by(&self, count: usize, value: impl Fn(&DirectoryStats) -> u64) -> Vec<&DirectoryStats> {
+        let mut directories: Vec<&DirectoryStats> =
+            self.all.values().filter(|d| value(d) > 0).collect();
+        directories.sort_by(|a, b| value(b).cmp(&value(a)).then(a.inode.cmp(&b.inode)));
+        directories.truncate(count);
+        directories
+    }
+
+    /// Get the statistics of a directory, inserting them if it is not known yet
+    fn stats_mut(&mut self, inode: u64) -> &mut DirectoryStats {
+        self.all.entry(inode).or_insert(DirectoryStats {
+            inode,
+            ..Default::default()
+        })
+    }
+}
@@ -0,0 +1,118 @@
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::NaiveDateTime;
+
+use super::line_parser::SetGoal;
+
+/// Statistics of the SETGOAL operations applying a single goal
+#[derive(Debug, Default)]
+pub struct GoalStats {
+    /// The number of SETGOAL operations
+    pub operations: u64,
+    /// The number of inodes whose goal changed
+    pub changed: u64,
+}
+
+/// An inode whose goal is set by a SETGOAL operation
+#[derive(Debug)]
+pub struct GoalTarget {
+    /// The inode number
+    pub inode: u64,
+    /// The size of the inode at the time of the change, if it is a file of known size
+    pub size: Option<u64>,
+    /// Whether the inode is known to be a directory
+    pub directory: bool,
+}
+
+/// A goal change of a single inode, set directly or by a recursive change of a parent directory
+#[derive(Debug)]
+pub struct GoalChange {
+    /// The inode number
+    pub inode: u64,
+    /// The previous goal, if known
+    pub old: Option<u32>,
+    /// The new goal
+    pub new: u32,
+    /// The size of the inode at the time of the change, if it is a file of known size
+    pub size: Option<u64>,
+}
+
+/// A recursive goal change of a directory
+#[derive(Debug)]
+pub struct RecursiveGoalChange {
+    /// The timestamp of the change
+    pub timestamp: NaiveDateTime,
+    /// The directory inode number
+    pub inode: u64,
+    /// The new goal
+    pub goal: u32,
+    /// The number of inodes whose goal changed
+    pub changed: u64,
+    /// The number of known inodes below the directory whose goal changed
+    pub descendants: u64,
+}
+
+/// Struct to track the goal of every inode seen in SETGOAL operations and the goal changes
+#[derive(Debug, Default)]
+pub struct Goals {
+    /// Statistics of the applied goals by goal
+    pub applied: BTreeMap<u32, GoalStats>,
+    /// All goal changes of single inodes, including those of the known inodes below a
+    /// directory changed recursively
+    pub changes: Vec<GoalChange>,
+    /// All recursive goal changes
+    pub recursive: Vec<RecursiveGoalChange>,
+    /// HashMap to hold the latest known goal of each inode
+    known: HashMap<u64, u32>,
+}
+
+impl Goals {
+    /// Create a new Goals struct without any known goals
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a SETGOAL operation. `targets` are the inode itself and, for a recursive change,
+    /// all known inodes below it. The inode itself is recorded as changed if the operation changed
+    /// any inode, except for a recursively changed directory, whose count covers the inodes below
+    /// it. Directories below it get the new goal, but are not recorded as changes, as they hold
+    /// no data.
+    pub fn set(&mut self, set_goal: &SetGoal, targets: &[GoalTarget], timestamp: NaiveDateTime) {
+        let stats = self.applied.entry(set_goal.goal).or_default();
+        stats.operations += 1;
+        stats.changed += set_goal.changed;
+
+        let mut descendants = 0;
+        for target in targets.iter() {
+            let old = self.known.insert(target.inode, set_goal.goal);
+            let changed = if target.inode == set_goal.inode {
+                set_goal.changed > 0 && !(set_goal.recursive && target.directory)
+            } else {
+                descendants += u64::from(old != Some(set_goal.goal));
+                !target.directory && old != Some(set_goal.goal)
+            };
+            if changed {
+                self.changes.push(GoalChange {
+                    inode: target.inode,
+                    old,
+                    new: set_goal.goal,
+                    size: target.size,
+                });
+            }
+        }
+        if set_goal.recursive {
+            self.recursive.push(RecursiveGoalChange {
+                timestamp,
+                inode: set_goal.inode,
+                goal: set_goal.goal,
+                changed: set_goal.changed,
+                descendants,
+            });
+        }
+    }
+
+    /// Return the latest known goal of an inode
+    pub fn get(&self, inode: u64) -> Option<u32> {
+        self.known.get(&inode).copied()
+    }
+}
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use chrono::NaiveDateTime;
 
@@ -9,6 +9,13 @@ pub struct Inodes {
     pub all: Vec<Inode>,
     /// HashMap to hold all currently active inodes
     active: HashMap<u64, Inode>,
+    /// HashMap to hold the number of times each inode number was created, which is the
+    /// generation of the latest inode with that number
+    generations: HashMap<u64, u64>,
+    /// HashMap to hold the number of names of inodes whose link count is known
+    links: HashMap<u64, u64>,
+    /// HashSet to hold the inode numbers whose latest inode was deleted and not created again
+    deleted: HashSet<u64>,
 }
 
 impl Inodes {
@@ -17,50 +24,197 @@ impl Inodes {
         Self::default()
     }
 
-    /// Append an inode to the active hashmap
+    /// Append a new generation of an inode to the active hashmap. If the inode number is still
+    /// active, its deletion was not seen, so the previous generation is moved to the all vector
+    /// instead of being mixed with the new one.
     pub fn append(&mut self, inode: u64, timestamp: Option<chrono::NaiveDateTime>) {
-        self.active.entry(inode).or_insert(Inode {
+        let generation = self.generations.entry(inode).or_insert(0);
+        *generation += 1;
+        let previous = self.active.insert(
             inode,
-            created: timestamp,
-            ..Default::default()
-        });
+            Inode {
+                inode,
+                generation: *generation,
+                created: timestamp,
+                ..Default::default()
+            },
+        );
+        if let Some(previous) = previous {
+            self.all.push(previous);
+        }
+        self.links.insert(inode, 1);
+        self.deleted.remove(&inode);
+    }
+
+    /// Count a new name of an inode from a LINK operation. An inode whose link count is not
+    /// known had at least one name before.
+    pub fn link(&mut self, inode: u64) {
+        *self.links.entry(inode).or_insert(1) += 1;
+    }
+
+    /// Count a removed name of an inode. Returns whether it was the last name, so the inode is
+    /// deleted. Inodes whose link count is not known are assumed to have had a single name.
+    pub fn unlink(&mut self, inode: u64) -> bool {
+        match self.links.get_mut(&inode) {
+            Some(links) if *links > 1 => {
+                *links -= 1;
+                false
+            }
+            _ => {
+                self.links.remove(&inode);
+                true
+            }
+        }
+    }
+
+    /// The number of names of an inode, if its link count is known
+    pub fn links(&self, inode: u64) -> Option<u64> {
+        self.links.get(&inode).copied()
+    }
+
+    /// Find a specific generation of an inode number
+    pub fn find(&self, inode: u64, generation: u64) -> Option<&Inode> {
+        self.generations(inode)
+            .into_iter()
+            .find(|i| i.generation == generation)
     }
 
-    /// Remove an inode from the active hashmap and append it to the all vector
+    /// Return all known generations of an inode number, ordered by generation
+    pub fn generations(&self, inode: u64) -> Vec<&Inode> {
+        let mut generations: Vec<&Inode> = self
+            .all
+            .iter()
+            .chain(self.active.get(&inode))
+            .filter(|i| i.inode == inode)
+            .collect();
+        generations.sort_by_key(|i| i.generation);
+        generations
+    }
+
+    /// Get a reference to a currently active inode, if any
+    pub fn active(&self, inode: u64) -> Option<&Inode> {
+        self.active.get(&inode)
+    }
+
+    /// Get a mutable reference to a currently active inode, if any
+    pub fn active_mut(&mut self, inode: u64) -> Option<&mut Inode> {
+        self.active.get_mut(&inode)
+    }
+
+    /// Remove an inode from the active hashmap and append it to the all vector. The deleting
+    /// operation is counted on the removed inode.
     pub fn delete(&mut self, inode: u64, timestamp: Option<chrono::NaiveDateTime>) {
-        if let Some(deleted_inode) = self.active.remove(&inode) {
+        self.deleted.insert(inode);
+        if let Some(mut deleted_inode) = self.active.remove(&inode) {
+            deleted_inode.deleted = timestamp;
+            deleted_inode.operations += 1;
             self.all.push(deleted_inode);
         } else {
             self.all.push(Inode {
                 inode,
+                generation: self.current_generation(inode),
                 deleted: timestamp,
+                operations: 1,
                 ..Default::default()
             })
         }
     }
 
-    /// Update the length of an inode and the amount of data written to it.
-    pub fn update_length(&mut self, inode: u64, length: u64) {
+    /// Restore a deleted inode from an UNDEL operation. The latest generation is moved back from
+    /// the all vector to the active hashmap, if its deletion was seen, with a single name.
+    pub fn undelete(&mut self, inode: u64) {
+        if !self.deleted.remove(&inode) {
+            return;
+        }
+        let generation = self.current_generation(inode);
+        if let Some(index) = self
+            .all
+            .iter()
+            .rposition(|i| i.inode == inode && i.generation == generation)
+        {
+            let mut restored = self.all.remove(index);
+            restored.deleted = None;
+            self.active.insert(inode, restored);
+            self.links.insert(inode, 1);
+        }
+    }
+
+    /// Count an operation on an inode. Inodes not tracked yet, i.e. created before the analyzed
+    /// range, start to be tracked with an unknown length. Operations on deleted inodes are
+    /// ignored, as the deleting operation is counted by `delete`.
+    pub fn record_operation(&mut self, inode: u64) {
+        if self.deleted.contains(&inode) {
+            return;
+        }
+        let generation = self.current_generation(inode);
+        self.active
+            .entry(inode)
+            .or_insert(Inode {
+                inode,
+                generation,
+                length_unknown: true,
+                ..Default::default()
+            })
+            .operations += 1;
+    }
+
+    /// Update the length of an inode and the amount of data written to or truncated from it.
+    /// Returns the amount of data written by this update, which is 0 for inodes not tracked yet
+    /// or whose length is not known yet.
+    pub fn update_length(&mut self, inode: u64, length: u64) -> u64 {
         if let Some(i) = self.active.get_mut(&inode) {
-            if i.last_known_length == 0 {
-                i.written += length;
-            } else if length > i.last_known_length {
-                i.written += length - i.last_known_length;
+            if i.length_unknown {
+                i.length_unknown = false;
+                i.last_known_length = length;
+                return 0;
+            }
+            let written = length.saturating_sub(i.last_known_length);
+            i.written += written;
+            i.truncated += i.last_known_length.saturating_sub(length);
+            if length == 0 && i.last_known_length > 0 {
+                i.truncated_to_zero = true;
+            } else if length > 0 && i.truncated_to_zero {
+                i.truncated_to_zero = false;
+                i.rewrites += 1;
             }
-            // We do not count truncuations for now
-            i.last_known_length = length
+            i.last_known_length = length;
+            written
         } else {
             self.active.insert(
                 inode,
                 Inode {
                     inode,
+                    generation: self.current_generation(inode),
                     last_known_length: length,
                     ..Default::default()
                 },
             );
+            0
         }
     }
 
+    /// Count a TRUNC operation on an inode. The truncated amount itself is taken from the LENGTH
+    /// operation following it. For an inode not tracked yet, the length before the TRUNC is not
+    /// known, so the following LENGTH only sets its length.
+    pub fn truncate(&mut self, inode: u64) {
+        let generation = self.current_generation(inode);
+        self.active
+            .entry(inode)
+            .or_insert(Inode {
+                inode,
+                generation,
+                length_unknown: true,
+                ..Default::default()
+            })
+            .truncations += 1;
+    }
+
+    /// The generation of the latest inode with the given number, 0 if it was never created in
+    /// the analyzed range
+    pub fn current_generation(&self, inode: u64) -> u64 {
+        self.generations.get(&inode).copied().unwrap_or(0)
+    }
+
     /// Drain the active hashmap and append all inodes to the all vector
     pub fn drain_active(&mut self) {
         for (_, i) in self.active.drain() {
@@ -72,17 +226,90 @@ impl Inodes {
 /// Struct to hold inode information
 /// Note that the inode is not unique, as it may be reused after deletion, so we can't use a
 /// hashmap for storing information for each inode. The `Inodes` struct is used to manage this.
+/// The inode number together with the generation identifies a single file or directory.
 #[derive(Debug, Default)]
 pub struct Inode {
     /// The inode number
     pub inode: u64,
+    /// The number of times the inode number was created up to this inode, 0 if it was created
+    /// before the analyzed range
+    pub generation: u64,
     /// The timestamp the inode, if known, was created
     pub created: Option<NaiveDateTime>,
     /// The timestamp the inode, if known, was deleted
     pub deleted: Option<NaiveDateTime>,
+    /// The name of the inode from the CREATE operation, if known
+    pub name: Option<String>,
+    /// The node type character from the CREATE operation, if known (e.g. 'f' or 'd'), or 'l'
+    /// for inodes created by SYMLINK
+    pub node_type: Option<char>,
+    /// The permission bits from the latest CREATE or ATTR operation, if known
+    pub mode: Option<u32>,
+    /// The owner uid from the latest CREATE or ATTR operation, if known
+    pub uid: Option<u32>,
+    /// The owner gid from the latest CREATE or ATTR operation, if known
+    pub gid: Option<u32>,
+    /// The goal from the latest SETGOAL operation, if known
+    pub goal: Option<u32>,
+    /// The path a symlink points to, from the SYMLINK operation
+    pub symlink_target: Option<String>,
     /// The last known length of the inode
     pub last_known_length: u64,
-    /// The amount of data written to the inode
-    /// Note that truncations are not counted
+    /// The amount of data written to the inode, i.e. the sum of all length increases. Length
+    /// decreases are counted in `truncated` instead.
     pub written: u64,
+    /// The amount of data truncated from the inode, i.e. the sum of all length decreases
+    pub truncated: u64,
+    /// The number of TRUNC operations on the inode
+    pub truncations: u64,
+    /// The number of rewrite cycles, i.e. the length dropping to 0 and growing again
+    pub rewrites: u64,
+    /// The number of operations of any type on the inode
+    pub operations: u64,
+    /// Whether the length dropped to 0 and a rewrite cycle may follow
+    truncated_to_zero: bool,
+    /// Whether the length is not known, because the inode is not created in the analyzed range
+    /// and no LENGTH operation was seen for it yet
+    length_unknown: bool,
+}
+
+impl Inode {
+    /// Whether the inode is a regular file. Inodes of unknown type are considered files if a
+    /// length was recorded for them.
+    pub fn is_file(&self) -> bool {
+        match self.node_type {
+            Some(t) => t == 'f',
+            None => self.last_known_length > 0,
+        }
+    }
+
+    /// The name of the inode, or a placeholder if it is not known
+    pub fn display_name(&self) -> &str {
+        self.name.as_deref().unwrap_or("<unknown>")
+    }
+
+    /// The extension of the inode name (the part after the last '.'), if any. Leading dots of
+    /// hidden files do not start an extension.
+    pub fn extension(&self) -> Option<&str> {
+        extension(self.name.as_deref()?)
+    }
+
+    /// The lifetime of the inode, if both the creation and deletion timestamps are known
+    pub fn lifetime(&self) -> Option<chrono::TimeDelta> {
+        Some(self.deleted? - self.created?)
+    }
+
+    /// The net size change of the inode, i.e. the written amount minus the truncated amount
+    pub fn net_size_change(&self) -> i64 {
+        self.written as i64 - self.truncated as i64
+    }
+}
+
+/// The extension of a name (the part after the last '.'), if any. Leading dots of hidden files
+/// do not start an extension.
+pub fn extension(name: &str) -> Option<&str> {
+    match name.rsplit_once('.') {
+        Some((stem, extension)) if !stem.is_empty() && !extension.is_empty() => Some(extension),
+        _ => None,
+    }
 }
@@ -34,6 +34,42 @@ impl<'a> Parser<'a> {
         })
     }
 
+    /// Return the inode the operation acts on, if any. Unlike `inode`, which is only the number
+    /// after the `):` (and may be a session or chunk id), this also covers operations taking the
+    /// inode as their first argument, like WRITE, TRUNC or ACQUIRE.
+    pub fn target_inode(&self) -> Option<u64> {
+        match self.operation.as_str() {
+            "CREATE" | "MOVE" | "SYMLINK" | "UNLINK" => self.inode,
+            "ACCESS" | "ACQUIRE" | "AMTIME" | "APPEND" | "ATTR" | "DELETEACL" | "LENGTH"
+            | "LINK" | "PURGE" | "RELEASE" | "REPAIR" | "SETACL" | "SETEATTR" | "SETGOAL"
+            | "SETTRASHTIME" | "SETXATTR" | "SNAPSHOT" | "TRUNC" | "UNDEL" | "WRITE" => {
+                argument(&self.arguments().ok()?, 0)
+            }
+            _ => None,
+        }
+    }
+
+    /// Return all inodes referenced by the operation: the target inode and, for namespace
+    /// operations, the parent directories involved.
+    pub fn referenced_inodes(&self) -> Vec<u64> {
+        let mut inodes: Vec<u64> = self.target_inode().into_iter().collect();
+        let parent_indexes: &[usize] = match self.operation.as_str() {
+            "CREATE" | "SYMLINK" | "UNLINK" => &[0],
+            "LINK" | "SNAPSHOT" => &[1],
+            "MOVE" => &[0, 2],
+            _ => &[],
+        };
+        let arguments = self.arguments().unwrap_or_default();
+        for index in parent_indexes {
+            if let Some(parent) = argument(&arguments, *index) {
+                if !inodes.contains(&parent) {
+                    inodes.push(parent);
+                }
+            }
+        }
+        inodes
+    }
+
     /// Parse the LENGTH operation and return the inode and length.
     pub fn parse_length(&self) -> Result<(u64, u64), &'static str> {
         let start = self
@@ -59,15 +95,521 @@ impl<'a> Parser<'a> {
         Ok((inode, length))
     }
 
-    /// Parse the line for a directory or file and update the counters.
-    /// Only works for CREATE operations.
-    pub fn parse_line_for_dir_file(&self, dirs: &mut u64, files: &mut u64) {
-        if self.line.contains(",d,") {
-            *dirs += 1;
-        } else if self.line.contains(",f,") {
-            *files += 1;
+    /// Parse the CREATE operation and return its fields.
+    pub fn parse_create(&self) -> Result<Create, &'static str> {
+        let arguments = self.arguments()?;
+        if arguments.len() < 6 {
+            return Err("Not enough arguments in create operation line");
+        }
+        Ok(Create {
+            parent: parse_argument(&arguments, 0, "Failed to parse parent in create operation.")?,
+            name: decode_name(arguments[1]),
+            node_type: arguments[2]
+                .chars()
+                .next()
+                .ok_or("Could not find node type in create operation line")?,
+            mode: parse_argument(&arguments, 3, "Failed to parse mode in create operation.")?,
+            uid: parse_argument(&arguments, 4, "Failed to parse uid in create operation.")?,
+            gid: parse_argument(&arguments, 5, "Failed to parse gid in create operation.")?,
+        })
+    }
+
+    /// Parse the SYMLINK operation and return its fields.
+    pub fn parse_symlink(&self) -> Result<Symlink, &'static str> {
+        let arguments = self.arguments()?;
+        if arguments.len() < 5 {
+            return Err("Not enough arguments in symlink operation line");
+        }
+        Ok(Symlink {
+            parent: parse_argument(
+                &arguments,
+                0,
+                "Failed to parse parent in symlink operation.",
+            )?,
+            name: decode_name(arguments[1]),
+            target: decode_name(arguments[2]),
+            uid: parse_argument(&arguments, 3, "Failed to parse uid in symlink operation.")?,
+            gid: parse_argument(&arguments, 4, "Failed to parse gid in symlink operation.")?,
+        })
+    }
+
+    /// Parse the LINK operation and return its fields.
+    pub fn parse_link(&self) -> Result<Link, &'static str> {
+        let arguments = self.arguments()?;
+        if arguments.len() < 3 {
+            return Err("Not enough arguments in link operation line");
         }
+        Ok(Link {
+            inode: parse_argument(&arguments, 0, "Failed to parse inode in link operation.")?,
+            parent: parse_argument(&arguments, 1, "Failed to parse parent in link operation.")?,
+            name: decode_name(arguments[2]),
+        })
+    }
+
+    /// Parse the ATTR operation and return its fields.
+    pub fn parse_attr(&self) -> Result<Attr, &'static str> {
+        let arguments = self.arguments()?;
+        Ok(Attr {
+            inode: parse_argument(&arguments, 0, "Failed to parse inode in attr operation.")?,
+            mode: parse_argument(&arguments, 1, "Failed to parse mode in attr operation.")?,
+            uid: parse_argument(&arguments, 2, "Failed to parse uid in attr operation.")?,
+            gid: parse_argument(&arguments, 3, "Failed to parse gid in attr operation.")?,
+        })
+    }
+
+    /// Parse the MOVE operation and return its fields.
+    pub fn parse_move(&self) -> Result<Move, &'static str> {
+        let arguments = self.arguments()?;
+        if arguments.len() < 4 {
+            return Err("Not enough arguments in move operation line");
+        }
+        Ok(Move {
+            inode: self
+                .inode
+                .ok_or("Could not find inode in move operation line")?,
+            src_parent: parse_argument(&arguments, 0, "Failed to parse parent in move operation.")?,
+            src_name: decode_name(arguments[1]),
+            dst_parent: parse_argument(&arguments, 2, "Failed to parse parent in move operation.")?,
+            dst_name: decode_name(arguments[3]),
+        })
+    }
+
+    /// Parse the SNAPSHOT operation and return its fields.
+    pub fn parse_snapshot(&self) -> Result<Snapshot, &'static str> {
+        let arguments = self.arguments()?;
+        if arguments.len() < 4 {
+            return Err("Not enough arguments in snapshot operation line");
+        }
+        let can_overwrite: u8 = parse_argument(
+            &arguments,
+            3,
+            "Failed to parse overwrite flag in snapshot operation.",
+        )?;
+        Ok(Snapshot {
+            source: parse_argument(
+                &arguments,
+                0,
+                "Failed to parse source in snapshot operation.",
+            )?,
+            dst_parent: parse_argument(
+                &arguments,
+                1,
+                "Failed to parse parent in snapshot operation.",
+            )?,
+            name: decode_name(arguments[2]),
+            can_overwrite: can_overwrite != 0,
+        })
+    }
+
+    /// Parse the SETXATTR operation and return its fields.
+    pub fn parse_setxattr(&self) -> Result<SetXattr, &'static str> {
+        let arguments = self.arguments()?;
+        if arguments.len() < 4 {
+            return Err("Not enough arguments in setxattr operation line");
+        }
+        let mode: u8 =
+            parse_argument(&arguments, 3, "Failed to parse mode in setxattr operation.")?;
+        Ok(SetXattr {
+            inode: parse_argument(
+                &arguments,
+                0,
+                "Failed to parse inode in setxattr operation.",
+            )?,
+            name: decode_name(arguments[1]),
+            value_length: decode_name(arguments[2]).len() as u64,
+            remove: mode == XATTR_SMODE_REMOVE,
+        })
+    }
+
+    /// Parse the SETACL or DELETEACL operation and return its fields. The ACL of SETACL may
+    /// contain commas, so all remaining arguments are part of it.
+    pub fn parse_acl(&self) -> Result<Acl, &'static str> {
+        let arguments = self.arguments()?;
+        if arguments.len() < 2 {
+            return Err("Not enough arguments in acl operation line");
+        }
+        Ok(Acl {
+            inode: parse_argument(&arguments, 0, "Failed to parse inode in acl operation.")?,
+            acl_type: arguments[1].to_string(),
+            acl: (self.operation == "SETACL").then(|| decode_name(&arguments[2..].join(","))),
+        })
+    }
+
+    /// Parse the SETQUOTA operation and return its fields.
+    pub fn parse_setquota(&self) -> Result<SetQuota, &'static str> {
+        let arguments = self.arguments()?;
+        if arguments.len() < 5 {
+            return Err("Not enough arguments in setquota operation line");
+        }
+        Ok(SetQuota {
+            hard: arguments[0] == "H",
+            inodes: arguments[1] == "i",
+            group: arguments[2] == "g",
+            owner: parse_argument(
+                &arguments,
+                3,
+                "Failed to parse owner in setquota operation.",
+            )?,
+            limit: parse_argument(
+                &arguments,
+                4,
+                "Failed to parse limit in setquota operation.",
+            )?,
+        })
+    }
+
+    /// Parse the SETGOAL operation and return its fields.
+    pub fn parse_setgoal(&self) -> Result<SetGoal, &'static str> {
+        let arguments = self.arguments()?;
+        let smode: u32 =
+            parse_argument(&arguments, 3, "Failed to parse mode in setgoal operation.")?;
+        Ok(SetGoal {
+            inode: parse_argument(&arguments, 0, "Failed to parse inode in setgoal operation.")?,
+            goal: parse_argument(&arguments, 2, "Failed to parse goal in setgoal operation.")?,
+            recursive: smode & SMODE_RECURSIVE != 0,
+            changed: argument(&self.results(), 0).unwrap_or(0),
+        })
+    }
+
+    /// Parse the SETTRASHTIME operation and return its fields.
+    pub fn parse_settrashtime(&self) -> Result<SetTrashTime, &'static str> {
+        let arguments = self.arguments()?;
+        let smode: u32 = parse_argument(
+            &arguments,
+            3,
+            "Failed to parse mode in settrashtime operation.",
+        )?;
+        Ok(SetTrashTime {
+            inode: parse_argument(
+                &arguments,
+                0,
+                "Failed to parse inode in settrashtime operation.",
+            )?,
+            trash_time: parse_argument(
+                &arguments,
+                2,
+                "Failed to parse trash time in settrashtime operation.",
+            )?,
+            recursive: smode & SMODE_RECURSIVE != 0,
+            changed: argument(&self.results(), 0).unwrap_or(0),
+        })
+    }
+
+    /// The comma separated results after "):", e.g. the changed, not changed and not permitted
+    /// counts of SETGOAL
+    fn results(&self) -> Vec<&str> {
+        self.line
+            .split_once("):")
+            .map_or(Vec::new(), |(_, results)| {
+                results.trim().split(',').collect()
+            })
+    }
+
+    /// Parse the TRUNC operation and return its fields.
+    pub fn parse_trunc(&self) -> Result<Trunc, &'static str> {
+        let arguments = self.arguments()?;
+        Ok(Trunc {
+            inode: parse_argument(&arguments, 0, "Failed to parse inode in trunc operation.")?,
+            index: parse_argument(&arguments, 1, "Failed to parse index in trunc operation.")?,
+            chunk_id: parse_result(self.line)
+                .ok_or("Failed to parse chunk id in trunc operation.")?,
+        })
+    }
+
+    /// Parse the WRITE operation and return its fields.
+    pub fn parse_write(&self) -> Result<Write, &'static str> {
+        let arguments = self.arguments()?;
+        let opflag: u8 = parse_argument(&arguments, 2, "Failed to parse flag in write operation.")?;
+        Ok(Write {
+            inode: parse_argument(&arguments, 0, "Failed to parse inode in write operation.")?,
+            index: parse_argument(&arguments, 1, "Failed to parse index in write operation.")?,
+            new_chunk: opflag == 1,
+            chunk_id: parse_result(self.line)
+                .ok_or("Failed to parse chunk id in write operation.")?,
+        })
+    }
+
+    /// Parse the UNLINK operation and return the parent inode and the decoded name.
+    pub fn parse_unlink(&self) -> Result<(u64, String), &'static str> {
+        let arguments = self.arguments()?;
+        if arguments.len() < 2 {
+            return Err("Not enough arguments in unlink operation line");
+        }
+        Ok((
+            parse_argument(&arguments, 0, "Failed to parse parent in unlink operation.")?,
+            decode_name(arguments[1]),
+        ))
+    }
+
+    /// Parse the ACQUIRE or RELEASE operation and return the inode and session id.
+    pub fn parse_acquire(&self) -> Result<(u64, u64), &'static str> {
+        let arguments = self.arguments()?;
+        Ok((
+            parse_argument(&arguments, 0, "Failed to parse inode in acquire operation.")?,
+            parse_argument(
+                &arguments,
+                1,
+                "Failed to parse session in acquire operation.",
+            )?,
+        ))
+    }
+
+    /// Parse the SESSION operation and return the new session id.
+    pub fn parse_session(&self) -> Result<u64, &'static str> {
+        parse_result(self.line).ok_or("Failed to parse session id in session operation.")
+    }
+
+    /// Parse an operation with a single numeric argument, like UNLOCK or NEXTCHUNKID, and return
+    /// the argument.
+    pub fn parse_single(&self) -> Result<u64, &'static str> {
+        let arguments = self.arguments()?;
+        parse_argument(&arguments, 0, "Failed to parse argument into u64.")
     }
+
+    /// Return the comma separated arguments between the parentheses of the operation.
+    pub fn arguments(&self) -> Result<Vec<&'a str>, &'static str> {
+        let start = self
+            .line
+            .find('(')
+            .ok_or("Could not find '(' in operation line")?;
+        let end = self
+            .line
+            .rfind(')')
+            .ok_or("Could not find ')' in operation line")?;
+        if end < start {
+            return Err("Found ')' before '(' in operation line");
+        }
+        Ok(self.line[start + 1..end].split(',').collect())
+    }
+}
+
+/// Fields of a CREATE operation
+#[derive(Debug)]
+pub struct Create {
+    /// The inode of the parent directory
+    pub parent: u64,
+    /// The decoded name of the created entry
+    pub name: String,
+    /// The node type character, e.g. 'f' for files and 'd' for directories
+    pub node_type: char,
+    /// The permission bits of the created entry
+    pub mode: u32,
+    /// The owner uid of the created entry
+    pub uid: u32,
+    /// The owner gid of the created entry
+    pub gid: u32,
+}
+
+/// Fields of a SYMLINK operation
+#[derive(Debug)]
+pub struct Symlink {
+    /// The inode of the parent directory
+    pub parent: u64,
+    /// The decoded name of the symlink
+    pub name: String,
+    /// The decoded path the symlink points to
+    pub target: String,
+    /// The owner uid of the symlink
+    pub uid: u32,
+    /// The owner gid of the symlink
+    pub gid: u32,
+}
+
+/// Fields of a LINK operation
+#[derive(Debug)]
+pub struct Link {
+    /// The linked inode
+    pub inode: u64,
+    /// The inode of the parent directory of the new name
+    pub parent: u64,
+    /// The decoded new name
+    pub name: String,
+}
+
+/// Fields of an ATTR operation
+#[derive(Debug)]
+pub struct Attr {
+    /// The inode whose attributes changed
+    pub inode: u64,
+    /// The new permission bits
+    pub mode: u32,
+    /// The new owner uid
+    pub uid: u32,
+    /// The new owner gid
+    pub gid: u32,
+}
+
+/// Fields of a MOVE operation
+#[derive(Debug)]
+pub struct Move {
+    /// The moved inode
+    pub inode: u64,
+    /// The inode of the source parent directory
+    pub src_parent: u64,
+    /// The decoded source name
+    pub src_name: String,
+    /// The inode of the destination parent directory
+    pub dst_parent: u64,
+    /// The decoded destination name
+    pub dst_name: String,
+}
+
+/// Fields of a SNAPSHOT operation
+#[derive(Debug)]
+pub struct Snapshot {
+    /// The inode of the snapshotted file or directory
+    pub source: u64,
+    /// The inode of the destination parent directory
+    pub dst_parent: u64,
+    /// The decoded name of the snapshot in the destination directory
+    pub name: String,
+    /// Whether an existing destination may be overwritten
+    pub can_overwrite: bool,
+}
+
+/// Mode of SETXATTR operations removing the attribute
+const XATTR_SMODE_REMOVE: u8 = 3;
+
+/// Fields of a SETXATTR operation
+#[derive(Debug)]
+pub struct SetXattr {
+    /// The inode whose extended attribute changed
+    pub inode: u64,
+    /// The decoded attribute name
+    pub name: String,
+    /// The length of the decoded attribute value
+    pub value_length: u64,
+    /// Whether the attribute was removed
+    pub remove: bool,
+}
+
+/// Fields of a SETACL or DELETEACL operation
+#[derive(Debug)]
+pub struct Acl {
+    /// The inode whose ACL changed
+    pub inode: u64,
+    /// The ACL type as written in the changelog, e.g. access or default
+    pub acl_type: String,
+    /// The new ACL, or None if it was deleted
+    pub acl: Option<String>,
+}
+
+/// Fields of a SETQUOTA operation, e.g. `SETQUOTA(S,s,u,1000,1073741824)`
+#[derive(Debug, Clone, Copy)]
+pub struct SetQuota {
+    /// Whether the hard limit ('H') was set, otherwise the soft limit ('S')
+    pub hard: bool,
+    /// Whether the inode limit ('i') was set, otherwise the size limit ('s')
+    pub inodes: bool,
+    /// Whether the limit of a group ('g') was set, otherwise of a user ('u')
+    pub group: bool,
+    /// The uid or gid
+    pub owner: u32,
+    /// The new limit, 0 meaning no limit
+    pub limit: u64,
+}
+
+/// Flag in the mode of SETGOAL-like operations, marking them as applied recursively
+const SMODE_RECURSIVE: u32 = 0x04;
+
+/// Fields of a SETGOAL operation
+#[derive(Debug)]
+pub struct SetGoal {
+    /// The inode the goal was set on
+    pub inode: u64,
+    /// The new goal
+    pub goal: u32,
+    /// Whether the goal was set recursively on a directory
+    pub recursive: bool,
+    /// The number of inodes whose goal changed
+    pub changed: u64,
+}
+
+/// Fields of a SETTRASHTIME operation
+#[derive(Debug)]
+pub struct SetTrashTime {
+    /// The inode the trash time was set on
+    pub inode: u64,
+    /// The new trash time in seconds
+    pub trash_time: u32,
+    /// Whether the trash time was set recursively on a directory
+    pub recursive: bool,
+    /// The number of inodes whose trash time changed
+    pub changed: u64,
+}
+
+/// Fields of a TRUNC operation
+#[derive(Debug)]
+pub struct Trunc {
+    /// The truncated inode
+    pub inode: u64,
+    /// The index of the truncated chunk in the file
+    pub index: u64,
+    /// The id of the truncated chunk
+    pub chunk_id: u64,
+}
+
+/// Fields of a WRITE operation
+#[derive(Debug)]
+pub struct Write {
+    /// The written inode
+    pub inode: u64,
+    /// The index of the written chunk in the file
+    pub index: u64,
+    /// Whether a new chunk was allocated for the write, i.e. the opflag is 1. Other non-zero
+    /// opflags mark changes of an existing chunk, so they are not counted as new chunks.
+    pub new_chunk: bool,
+    /// The id of the written chunk
+    pub chunk_id: u64,
+}
+
+/// Decode a name from the changelog. Special characters (like ',') are escaped by SaunaFS as
+/// `%XX` hexadecimal sequences.
+///
+/// # Arguments
+/// * `name` - The escaped name
+pub fn decode_name(name: &str) -> String {
+    let bytes = name.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 2 < bytes.len() {
+            if let Some(byte) = std::str::from_utf8(&bytes[i + 1..i + 3])
+                .ok()
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+            {
+                decoded.push(byte);
+                i += 3;
+                continue;
+            }
+        }
+        decoded.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Parse a numeric argument of an operation, returning `None` if it is missing or not a number.
+///
+/// # Arguments
+/// * `arguments` - The arguments of the operation
+/// * `index` - The index of the argument to parse
+fn argument<T: std::str::FromStr>(arguments: &[&str], index: usize) -> Option<T> {
+    arguments.get(index).and_then(|a| a.parse().ok())
+}
+
+/// Parse a numeric argument of an operation.
+///
+/// # Arguments
+/// * `arguments` - The arguments of the operation
+/// * `index` - The index of the argument to parse
+/// * `error` - The error to return if the argument is missing or not a number
+fn parse_argument<T: std::str::FromStr>(
+    arguments: &[&str],
+    index: usize,
+    error: &'static str,
+) -> Result<T, &'static str> {
+    argument(arguments, index).ok_or(error)
 }
 
 /// Parse the timestamp from a log line.
@@ -117,16 +659,18 @@ fn parse_inode(line: &str, operation: &str) -> Option<u64> {
         "WRITE" | "TRUNC" => return None,
         _ => (),
     }
+    parse_result(line)
+}
+
+/// Parse the number after the `):` at the end of a log line, if any.
+fn parse_result(line: &str) -> Option<u64> {
     let parts: Vec<&str> = line.split("):").collect();
     if parts.len() != 2 {
         return None;
     }
 
     let inode_str = parts[1].trim();
-    match inode_str.parse::<u64>() {
-        Ok(inode) => Some(inode),
-        Err(_) => None,
-    }
+    inode_str.parse::<u64>().ok()
 }
 
 /// Parse the operation from a log line.
@@ -161,3 +705,26 @@ fn test_parse_inodes() {
     assert_eq!(None, parse_inode(trunc, "TRUNC"));
     assert_eq!(Some(3), parse_inode(unlink, "UNLINK"));
 }
+
+#[test]
+fn test_target_inode() {
+    let target = |line| Parser::new(line).unwrap().target_inode();
+
+    assert_eq!(
+        Some(2),
+        target("5: 1710181938|CREATE(1,configuration.h,f,420,1000,1000,0):2")
+    );
+    assert_eq!(None, target("4: 1710181842|SESSION():1"));
+    assert_eq!(Some(3), target("33: 1710182099|WRITE(3,0,1,3033285594):15"));
+    assert_eq!(Some(4), target("72: 1710183175|TRUNC(4,0,0):16"));
+    assert_eq!(Some(2), target("6: 1710181938|ACQUIRE(2,1)"));
+    assert_eq!(None, target("9: 1710181938|UNLOCK(12)"));
+}
+
+#[test]
+fn test_decode_name() {
+    assert_eq!(decode_name("%2Cf%2C"), ",f,");
+    assert_eq!(decode_name("configuration.h"), "configuration.h");
+    assert_eq!(decode_name("100%"), "100%");
+    assert_eq!(decode_name("%zz"), "%zz");
+}
@@ -1,2 +1,15 @@
+pub mod activity;
+pub mod attributes;
+pub mod chunks;
+pub mod directories;
+pub mod goals;
 pub mod inodes;
 pub mod line_parser;
+pub mod namespace;
+pub mod node_types;
+pub mod quotas;
+pub mod renames;
+pub mod sessions;
+pub mod snapshots;
+pub mod trash;
+pub mod xattrs;
@@ -0,0 +1,192 @@
+use std::collections::{hash_map::Entry, HashMap, HashSet};
+
+/// The inode number of the root directory
+pub const ROOT_INODE: u64 = 1;
+
+/// Maximum depth of a resolved path, to avoid looping on inconsistent parent information
+const MAX_PATH_DEPTH: usize = 256;
+
+/// Struct to resolve inode numbers to paths, from the names and parents seen in namespace
+/// operations. Entries are kept after deletion, so paths of deleted inodes can still be
+/// resolved.
+#[derive(Debug, Default)]
+pub struct Namespace {
+    /// HashMap to hold the latest known parent and name of each inode
+    entries: HashMap<u64, NamespaceEntry>,
+    /// HashMap to hold the inodes currently linked under each parent, by name
+    by_parent: HashMap<u64, HashMap<String, u64>>,
+    /// HashMap to hold the additional names of hard linked inodes
+    hard_links: HashMap<u64, Vec<NamespaceEntry>>,
+}
+
+/// The parent and name of an inode
+#[derive(Debug, Clone)]
+pub struct NamespaceEntry {
+    /// The inode of the parent directory
+    pub parent: u64,
+    /// The name in the parent directory
+    pub name: String,
+}
+
+impl Namespace {
+    /// Create a new empty Namespace struct
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the parent and name of an inode, replacing any inode linked under the same name
+    pub fn insert(&mut self, inode: u64, parent: u64, name: String) {
+        if let Some(previous) = self.entries.get(&inode) {
+            let (previous_parent, previous_name) = (previous.parent, previous.name.clone());
+            self.remove_child(inode, previous_parent, &previous_name);
+        }
+        self.add_child(inode, parent, name.clone());
+        self.entries.insert(inode, NamespaceEntry { parent, name });
+    }
+
+    /// Add a hard link, i.e. an additional name, of an inode
+    pub fn link(&mut self, inode: u64, parent: u64, name: String) {
+        self.add_child(inode, parent, name.clone());
+        let entry = NamespaceEntry { parent, name };
+        match self.entries.entry(inode) {
+            Entry::Occupied(_) => self.hard_links.entry(inode).or_default().push(entry),
+            Entry::Vacant(vacant) => {
+                vacant.insert(entry);
+            }
+        }
+    }
+
+    /// Rename one name of an inode. If the source name is a hard link, only that link is moved.
+    pub fn rename(
+        &mut self,
+        inode: u64,
+        src_parent: u64,
+        src_name: &str,
+        parent: u64,
+        name: String,
+    ) {
+        if let Some(links) = self.hard_links.get_mut(&inode) {
+            if let Some(link) = links
+                .iter_mut()
+                .find(|l| l.parent == src_parent && l.name == src_name)
+            {
+                *link = NamespaceEntry {
+                    parent,
+                    name: name.clone(),
+                };
+                self.remove_child(inode, src_parent, src_name);
+                self.add_child(inode, parent, name);
+                return;
+            }
+        }
+        self.insert(inode, parent, name);
+    }
+
+    /// Remove a name of an inode from its parent. If the inode has other names, one of them
+    /// becomes its name, otherwise its entry is kept, so its path can still be resolved.
+    pub fn unlink(&mut self, inode: u64, parent: u64, name: &str) {
+        self.remove_child(inode, parent, name);
+        let Some(links) = self.hard_links.get_mut(&inode) else {
+            return;
+        };
+        let is_primary = self
+            .entries
+            .get(&inode)
+            .is_some_and(|e| e.parent == parent && e.name == name);
+        if is_primary {
+            if let Some(link) = links.pop() {
+                self.entries.insert(inode, link);
+            }
+        } else {
+            links.retain(|l| l.parent != parent || l.name != name);
+        }
+        if links.is_empty() {
+            self.hard_links.remove(&inode);
+        }
+    }
+
+    /// Get the inode currently linked under a parent and name, if known
+    pub fn lookup(&self, parent: u64, name: &str) -> Option<u64> {
+        self.by_parent.get(&parent)?.get(name).copied()
+    }
+
+    /// Get the latest known parent and name of an inode
+    pub fn get(&self, inode: u64) -> Option<&NamespaceEntry> {
+        self.entries.get(&inode)
+    }
+
+    /// Resolve the path of an inode as far as it is known. If an ancestor is not known, the path
+    /// starts with that ancestor's inode number, e.g. `<inode 12>/dir/file`, and an unknown inode
+    /// is resolved to just `<inode 12>`.
+    pub fn path(&self, inode: u64) -> String {
+        if inode == ROOT_INODE {
+            return "/".to_string();
+        }
+        let mut names = Vec::new();
+        let mut current = inode;
+        while current != ROOT_INODE && names.len() < MAX_PATH_DEPTH {
+            match self.entries.get(&current) {
+                Some(entry) => {
+                    names.push(entry.name.as_str());
+                    current = entry.parent;
+                }
+                None => break,
+            }
+        }
+        if names.is_empty() {
+            return format!("<inode {}>", current);
+        }
+        names.reverse();
+        let prefix = if current == ROOT_INODE {
+            String::new()
+        } else {
+            format!("<inode {}>", current)
+        };
+        format!("{}/{}", prefix, names.join("/"))
+    }
+
+    /// Return the inode and all known inodes currently linked below it, each hard linked inode
+    /// only once
+    pub fn subtree(&self, inode: u64) -> Vec<u64> {
+        let mut seen = HashSet::from([inode]);
+        let mut subtree = vec![inode];
+        let mut index = 0;
+        while index < subtree.len() {
+            if let Some(children) = self.by_parent.get(&subtree[index]) {
+                subtree.extend(children.values().filter(|c| seen.insert(**c)));
+            }
+            index += 1;
+        }
+        subtree
+    }
+
+    /// Link an inode under a parent and name, replacing any inode linked under the same name
+    fn add_child(&mut self, inode: u64, parent: u64, name: String) {
+        self.by_parent
+            .entry(parent)
+            .or_default()
+            .insert(name, inode);
+    }
+
+    /// Remove the given name from a parent, if the inode is still linked under it
+    fn remove_child(&mut self, inode: u64, parent: u64, name: &str) {
+        let Some(children) = self.by_parent.get_mut(&parent) else {
+            return;
+        };
+        if children.get(name) == Some(&inode) {
+            children.remove(name);
+            if children.is_empty() {
+                self.by_parent.remove(&parent);
+            }
+        }
+    }
+
+    /// Resolve the path of an entry with the given name in a parent directory
+    pub fn child_path(&self, parent: u64, name: &str) -> String {
+        let parent = self.path(parent);
+        match parent.as_str() {
+            "/" => format!("/{}", name),
+            _ => format!("{}/{}", parent, name),
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use std::collections::BTreeMap;
+
+/// The type of a node, from the type character of the CREATE operation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum NodeType {
+    /// A regular file, 'f'
+    File,
+    /// A directory, 'd'
+    Directory,
+    /// A symbolic link, 'l', created by SYMLINK operations
+    Symlink,
+    /// A named pipe, 'q'
+    Fifo,
+    /// A block device, 'b'
+    BlockDevice,
+    /// A character device, 'c'
+    CharDevice,
+    /// A socket, 's'
+    Socket,
+    /// A type character not known to this tool, e.g. from a newer SaunaFS version
+    Unknown(char),
+}
+
+impl NodeType {
+    /// Get the node type of a type character
+    pub fn from_char(node_type: char) -> Self {
+        match node_type {
+            'f' => Self::File,
+            'd' => Self::Directory,
+            'l' => Self::Symlink,
+            'q' => Self::Fifo,
+            'b' => Self::BlockDevice,
+            'c' => Self::CharDevice,
+            's' => Self::Socket,
+            other => Self::Unknown(other),
+        }
+    }
+
+    /// A human readable name of the node type
+    pub fn label(&self) -> String {
+        match self {
+            Self::File => "file".to_string(),
+            Self::Directory => "directory".to_string(),
+            Self::Symlink => "symlink".to_string(),
+            Self::Fifo => "fifo".to_string(),
+            Self::BlockDevice => "block device".to_string(),
+            Self::CharDevice => "char device".to_string(),
+            Self::Socket => "socket".to_string(),
+            Self::Unknown(c) => format!("unknown '{}'", c),
+        }
+    }
+}
+
+/// Struct to count the created nodes by type
+#[derive(Debug, Default)]
+pub struct NodeTypes {
+    /// The number of created nodes by type
+    pub created: BTreeMap<NodeType, u64>,
+}
+
+impl NodeTypes {
+    /// Create a new NodeTypes struct without any created nodes
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Count a created node and return its type
+    pub fn create(&mut self, node_type: char) -> NodeType {
+        let node_type = NodeType::from_char(node_type);
+        *self.created.entry(node_type).or_insert(0) += 1;
+        node_type
+    }
+
+    /// The number of created nodes of a type
+    pub fn count(&self, node_type: NodeType) -> u64 {
+        self.created.get(&node_type).copied().unwrap_or(0)
+    }
+
+    /// The number of created nodes with a type character not known to this tool
+    pub fn unknown(&self) -> u64 {
+        self.created
+            .iter()
+            .filter(|(t, _)| matches!(t, NodeType::Unknown(_)))
+            .map(|(_, count)| count)
+            .sum()
+    }
+}
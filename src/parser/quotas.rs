@@ -0,0 +1,55 @@
+use std::collections::HashMap;
+
+use chrono::NaiveDateTime;
+
+use super::line_parser::SetQuota;
+
+/// A change of a quota limit
+#[derive(Debug)]
+pub struct QuotaChange {
+    /// The timestamp of the change
+    pub timestamp: NaiveDateTime,
+    /// The new limit and whose limit it is
+    pub quota: SetQuota,
+    /// The previous limit, if it was set before in the analyzed range
+    pub old: Option<u64>,
+}
+
+/// Struct to track the SETQUOTA operations and the latest known limits
+#[derive(Debug, Default)]
+pub struct Quotas {
+    /// All quota changes, in the order they happened
+    pub changes: Vec<QuotaChange>,
+    /// HashMap to hold the latest known limit by group flag, owner, hard flag and inode flag
+    limits: HashMap<(bool, u32, bool, bool), u64>,
+}
+
+impl Quotas {
+    /// Create a new Quotas struct without any known limits
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a SETQUOTA operation
+    pub fn set(&mut self, quota: SetQuota, timestamp: NaiveDateTime) {
+        let old = self.limits.insert(
+            (quota.group, quota.owner, quota.hard, quota.inodes),
+            quota.limit,
+        );
+        self.changes.push(QuotaChange {
+            timestamp,
+            quota,
+            old,
+        });
+    }
+
+    /// The latest known size limit of a user or group, preferring the hard limit. Limits of 0
+    /// mean no limit and are ignored.
+    pub fn size_limit(&self, group: bool, owner: u32) -> Option<u64> {
+        [true, false]
+            .iter()
+            .filter_map(|hard| self.limits.get(&(group, owner, *hard, false)))
+            .copied()
+            .find(|limit| *limit > 0)
+    }
+}
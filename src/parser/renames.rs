@@ -0,0 +1,100 @@
+use std::collections::HashMap;
+
+use chrono::NaiveDateTime;
+
+use super::line_parser::Move;
+
+/// A file written under a temporary name and renamed over an existing file
+#[derive(Debug)]
+pub struct AtomicSave {
+    /// The timestamp of the rename
+    pub timestamp: NaiveDateTime,
+    /// The renamed inode
+    pub inode: u64,
+    /// The path the file was written as
+    pub temporary_path: String,
+    /// The path the file was renamed to
+    pub path: String,
+    /// The inode that was replaced by the rename
+    pub replaced: u64,
+}
+
+/// Struct to track MOVE operations, the paths each inode had and atomic-save patterns
+#[derive(Debug, Default)]
+pub struct Renames {
+    /// The number of MOVE operations
+    pub moves: u64,
+    /// The number of MOVE operations into a different directory
+    pub cross_directory: u64,
+    /// The number of MOVE operations replacing an existing entry
+    pub replacing: u64,
+    /// All renames of a newly written file over an existing file, in the order they happened
+    pub atomic_saves: Vec<AtomicSave>,
+    /// HashMap to hold the paths of every renamed inode, from the path before the first rename
+    /// to the latest one
+    lineage: HashMap<u64, Vec<String>>,
+}
+
+impl Renames {
+    /// Create a new Renames struct without any renames
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Forget the paths of an inode number, because it was created again
+    pub fn create(&mut self, inode: u64) {
+        self.lineage.remove(&inode);
+    }
+
+    /// Record a MOVE operation. `replaced` is the inode previously linked under the destination
+    /// name, if any, and `written` whether the moved inode was created and written in the
+    /// analyzed range, which makes a replacing rename an atomic save.
+    pub fn record(
+        &mut self,
+        moved: &Move,
+        src_path: String,
+        dst_path: String,
+        replaced: Option<u64>,
+        written: bool,
+        timestamp: NaiveDateTime,
+    ) {
+        self.moves += 1;
+        if moved.src_parent != moved.dst_parent {
+            self.cross_directory += 1;
+        }
+        if let Some(replaced) = replaced {
+            self.replacing += 1;
+            if written {
+                self.atomic_saves.push(AtomicSave {
+                    timestamp,
+                    inode: moved.inode,
+                    temporary_path: src_path.clone(),
+                    path: dst_path.clone(),
+                    replaced,
+                });
+            }
+        }
+        self.lineage
+            .entry(moved.inode)
+            .or_insert_with(|| vec![src_path])
+            .push(dst_path);
+    }
+
+    /// The paths of an inode, from the path before the first rename to the latest one, if it was
+    /// renamed
+    pub fn lineage(&self, inode: u64) -> Option<&[String]> {
+        self.lineage.get(&inode).map(|paths| paths.as_slice())
+    }
+
+    /// The renamed inodes with the most paths, at most `count`
+    pub fn most_renamed(&self, count: usize) -> Vec<(u64, &[String])> {
+        let mut lineages: Vec<(u64, &[String])> = self
+            .lineage
+            .iter()
+            .map(|(inode, paths)| (*inode, paths.as_slice()))
+            .collect();
+        lineages.sort_by(|a, b| b.1.len().cmp(&a.1.len()).then(a.0.cmp(&b.0)));
+        lineages.truncate(count);
+        lineages
+    }
+}
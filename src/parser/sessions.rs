@@ -0,0 +1,190 @@
+use std::collections::{hash_map::Entry, BTreeMap, HashMap, HashSet};
+
+use chrono::{DurationRound, NaiveDateTime, TimeDelta};
+
+/// Struct to manage sessions and the files they have open, from SESSION, ACQUIRE and RELEASE
+/// operations
+#[derive(Debug, Default)]
+pub struct Sessions {
+    /// HashMap to hold all sessions seen by their session id
+    pub all: HashMap<u64, Session>,
+    /// The amount of data written to inodes no session had open
+    pub unattributed_written: u64,
+    /// All closed file handles, in order of release
+    pub closed: Vec<OpenFile>,
+    /// HashMap to hold all currently open file handles by inode number and session id
+    open: HashMap<(u64, u64), OpenFile>,
+    /// HashMap to hold the sessions having each inode open, in the order they opened it
+    open_by_inode: HashMap<u64, Vec<u64>>,
+    /// The highest number of concurrently open file handles
+    pub peak_open: usize,
+    /// The timestamp the highest number of concurrently open file handles was first reached
+    pub peak_time: Option<NaiveDateTime>,
+    /// The highest number of concurrently open file handles in each hour
+    pub open_per_hour: BTreeMap<NaiveDateTime, usize>,
+}
+
+impl Sessions {
+    /// Create a new Sessions struct without any open files
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a SESSION operation creating a new session
+    pub fn create(&mut self, session: u64, timestamp: NaiveDateTime) {
+        self.session_mut(session, timestamp).created = Some(timestamp);
+    }
+
+    /// Open a file handle for a session. Acquiring a file the session already has open is
+    /// ignored.
+    pub fn acquire(&mut self, inode: u64, generation: u64, session: u64, timestamp: NaiveDateTime) {
+        if let Entry::Vacant(entry) = self.open.entry((inode, session)) {
+            entry.insert(OpenFile {
+                inode,
+                generation,
+                session,
+                opened: Some(timestamp),
+                closed: None,
+            });
+            self.open_by_inode.entry(inode).or_default().push(session);
+            self.session_mut(session, timestamp).files_opened += 1;
+        }
+        self.update_open_count(timestamp);
+    }
+
+    /// Close a file handle of a session. Handles opened before the analyzed range are recorded
+    /// without an open timestamp.
+    pub fn release(&mut self, inode: u64, generation: u64, session: u64, timestamp: NaiveDateTime) {
+        let mut handle = self.open.remove(&(inode, session)).unwrap_or(OpenFile {
+            inode,
+            generation,
+            session,
+            opened: None,
+            closed: None,
+        });
+        handle.closed = Some(timestamp);
+        self.closed.push(handle);
+        if let Some(sessions) = self.open_by_inode.get_mut(&inode) {
+            sessions.retain(|s| *s != session);
+            if sessions.is_empty() {
+                self.open_by_inode.remove(&inode);
+            }
+        }
+        self.session_mut(session, timestamp);
+        self.update_open_count(timestamp);
+    }
+
+    /// Record a WRITE operation on an inode for the session that opened it most recently
+    pub fn record_write(&mut self, inode: u64, generation: u64, timestamp: NaiveDateTime) {
+        if let Some(session) = self.opener(inode) {
+            self.session_mut(session, timestamp)
+                .written_inodes
+                .insert((inode, generation));
+        }
+    }
+
+    /// Record data written to an inode for the session that opened it most recently
+    pub fn record_written_bytes(&mut self, inode: u64, written: u64) {
+        match self.opener(inode).and_then(|s| self.all.get_mut(&s)) {
+            Some(session) => session.written += written,
+            None => self.unattributed_written += written,
+        }
+    }
+
+    /// Return all sessions, ordered by written data and opened files in descending order
+    pub fn by_activity(&self) -> Vec<&Session> {
+        let mut sessions: Vec<&Session> = self.all.values().collect();
+        sessions.sort_by(|a, b| {
+            b.written
+                .cmp(&a.written)
+                .then(b.files_opened.cmp(&a.files_opened))
+                .then(a.session.cmp(&b.session))
+        });
+        sessions
+    }
+
+    /// The session that most recently opened the inode and still has it open, if any
+    pub fn opener(&self, inode: u64) -> Option<u64> {
+        self.open_by_inode.get(&inode)?.last().copied()
+    }
+
+    /// Get a session, inserting it if it is not known yet, and update its activity range
+    fn session_mut(&mut self, session: u64, timestamp: NaiveDateTime) -> &mut Session {
+        let entry = self.all.entry(session).or_insert(Session {
+            session,
+            ..Default::default()
+        });
+        entry.first_seen.get_or_insert(timestamp);
+        entry.last_seen = Some(timestamp);
+        entry
+    }
+
+    /// Return all file handles still open, ordered by the time they were opened
+    pub fn still_open(&self) -> Vec<&OpenFile> {
+        let mut handles: Vec<&OpenFile> = self.open.values().collect();
+        handles.sort_by_key(|h| (h.opened, h.inode, h.session));
+        handles
+    }
+
+    /// Update the peak and hourly number of concurrently open file handles
+    fn update_open_count(&mut self, timestamp: NaiveDateTime) {
+        let open = self.open.len();
+        if open > self.peak_open {
+            self.peak_open = open;
+            self.peak_time = Some(timestamp);
+        }
+        let hour = timestamp
+            .duration_trunc(TimeDelta::hours(1))
+            .unwrap_or(timestamp);
+        let hourly = self.open_per_hour.entry(hour).or_insert(0);
+        *hourly = (*hourly).max(open);
+    }
+}
+
+/// Struct to hold session activity
+#[derive(Debug, Default)]
+pub struct Session {
+    /// The session id
+    pub session: u64,
+    /// The timestamp the session was created, if it was created in the analyzed range
+    pub created: Option<NaiveDateTime>,
+    /// The timestamp of the first operation of the session
+    pub first_seen: Option<NaiveDateTime>,
+    /// The timestamp of the last operation of the session
+    pub last_seen: Option<NaiveDateTime>,
+    /// The number of files opened by the session
+    pub files_opened: u64,
+    /// The inodes (number and generation) written to while the session had them open
+    pub written_inodes: HashSet<(u64, u64)>,
+    /// The amount of data written to inodes while the session had them open
+    pub written: u64,
+}
+
+impl Session {
+    /// How long the session was active, from its first to its last operation
+    pub fn lifetime(&self) -> Option<TimeDelta> {
+        Some(self.last_seen? - self.first_seen?)
+    }
+}
+
+/// Struct to hold a file handle, i.e. an inode opened by a session
+#[derive(Debug)]
+pub struct OpenFile {
+    /// The inode number
+    pub inode: u64,
+    /// The generation of the inode
+    pub generation: u64,
+    /// The session id holding the file open
+    pub session: u64,
+    /// The timestamp the file was opened, if it was opened in the analyzed range
+    pub opened: Option<NaiveDateTime>,
+    /// The timestamp the file was closed, if it was closed in the analyzed range
+    pub closed: Option<NaiveDateTime>,
+}
+
+impl OpenFile {
+    /// How long the file was open, if both the open and close timestamps are known
+    pub fn duration(&self) -> Option<TimeDelta> {
+        Some(self.closed? - self.opened?)
+    }
+}
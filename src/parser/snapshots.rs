@@ -0,0 +1,52 @@
+use chrono::NaiveDateTime;
+
+/// A SNAPSHOT operation cloning a file or a directory subtree
+#[derive(Debug)]
+pub struct SnapshotEvent {
+    /// The timestamp of the snapshot
+    pub timestamp: NaiveDateTime,
+    /// The inode of the snapshotted file or directory
+    pub source: u64,
+    /// The path of the snapshotted file or directory
+    pub source_path: String,
+    /// The path of the snapshot
+    pub path: String,
+    /// Whether an existing destination may be overwritten
+    pub can_overwrite: bool,
+    /// The number of inodes known to be in the snapshotted subtree, including the source
+    pub inodes: u64,
+    /// The amount of data known to be referenced by the snapshotted files
+    pub bytes: u64,
+}
+
+/// Struct to hold the SNAPSHOT operations. Snapshots create inodes without any CREATE
+/// operations, so the inodes and data referenced by the snapshots are estimated from the known
+/// namespace at the time of the snapshot. The estimates are lower bounds, as entries created
+/// before the analyzed range are not known.
+#[derive(Debug, Default)]
+pub struct Snapshots {
+    /// All snapshots in the order they were made
+    pub events: Vec<SnapshotEvent>,
+}
+
+impl Snapshots {
+    /// Create a new Snapshots struct without any snapshots
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a snapshot
+    pub fn record(&mut self, event: SnapshotEvent) {
+        self.events.push(event);
+    }
+
+    /// The estimated number of inodes created by all snapshots
+    pub fn inodes(&self) -> u64 {
+        self.events.iter().map(|e| e.inodes).sum()
+    }
+
+    /// The estimated amount of data referenced by all snapshots
+    pub fn bytes(&self) -> u64 {
+        self.events.iter().map(|e| e.bytes).sum()
+    }
+}
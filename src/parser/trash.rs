@@ -0,0 +1,166 @@
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::{DurationRound, NaiveDateTime, TimeDelta};
+
+use super::line_parser::SetTrashTime;
+
+/// A file moved to trash by an UNLINK operation
+#[derive(Debug)]
+pub struct TrashEntry {
+    /// The inode number
+    pub inode: u64,
+    /// The generation of the inode
+    pub generation: u64,
+    /// The path of the file before it was unlinked
+    pub path: String,
+    /// The parent directory the file was unlinked from
+    pub parent: u64,
+    /// The name the file was unlinked from
+    pub name: String,
+    /// The size of the file, if known
+    pub size: Option<u64>,
+    /// Whether the inode is known to be a regular file. The type of an inode created before the
+    /// analyzed range is not known, so it may also be a directory, which never stays in trash.
+    pub known_file: bool,
+    /// The owner uid, if known
+    pub uid: Option<u32>,
+    /// The owner gid, if known
+    pub gid: Option<u32>,
+    /// The trash time of the file in seconds, if known
+    pub trash_time: Option<u32>,
+    /// The timestamp the file was unlinked
+    pub deleted: NaiveDateTime,
+    /// The timestamp the file was purged from trash, if it was
+    pub purged: Option<NaiveDateTime>,
+    /// The timestamp the file was restored from trash, if it was
+    pub undeleted: Option<NaiveDateTime>,
+}
+
+impl TrashEntry {
+    /// Whether the file is still in trash at the end of the analyzed range
+    pub fn in_trash(&self) -> bool {
+        self.purged.is_none() && self.undeleted.is_none()
+    }
+
+    /// The timestamp the trash time of the file expires, if it is known
+    pub fn expires(&self) -> Option<NaiveDateTime> {
+        Some(self.deleted + TimeDelta::seconds(self.trash_time? as i64))
+    }
+}
+
+/// Struct to track the files in trash, from UNLINK to PURGE or UNDEL, and the trash times set
+#[derive(Debug, Default)]
+pub struct Trash {
+    /// All files moved to trash, in the order they were unlinked
+    pub entries: Vec<TrashEntry>,
+    /// The number of SETTRASHTIME operations by trash time
+    pub trash_times_applied: BTreeMap<u32, u64>,
+    /// The number of unlinked files that were removed immediately, because their trash time is 0
+    pub removed_immediately: u64,
+    /// The number of PURGE operations on files unlinked before the analyzed range
+    pub unknown_purges: u64,
+    /// The number of UNDEL operations on files unlinked before the analyzed range
+    pub unknown_undeletes: u64,
+    /// The amount of data of known size currently in trash
+    pub bytes: u64,
+    /// The highest amount of data of known size in trash
+    pub peak_bytes: u64,
+    /// The timestamp the highest amount of data in trash was first reached
+    pub peak_time: Option<NaiveDateTime>,
+    /// The highest amount of data of known size in trash in each hour
+    pub bytes_per_hour: BTreeMap<NaiveDateTime, u64>,
+    /// HashMap to hold the index of the entry of every inode currently in trash
+    in_trash: HashMap<u64, usize>,
+    /// HashMap to hold the latest known trash time of each inode
+    known: HashMap<u64, u32>,
+}
+
+impl Trash {
+    /// Create a new Trash struct without any files in trash
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a SETTRASHTIME operation. `inodes` are the inode itself and, for a recursive
+    /// change, all known inodes below it.
+    pub fn set_trash_time(&mut self, set_trash_time: &SetTrashTime, inodes: &[u64]) {
+        *self
+            .trash_times_applied
+            .entry(set_trash_time.trash_time)
+            .or_insert(0) += 1;
+        for inode in inodes.iter() {
+            self.known.insert(*inode, set_trash_time.trash_time);
+        }
+    }
+
+    /// Let a newly created inode inherit the trash time of its parent, if it is known
+    pub fn inherit(&mut self, inode: u64, parent: u64) {
+        match self.known.get(&parent).copied() {
+            Some(trash_time) => self.known.insert(inode, trash_time),
+            None => self.known.remove(&inode),
+        };
+    }
+
+    /// Record an unlinked file. Files with a trash time of 0 are removed immediately, all other
+    /// files are moved to trash.
+    pub fn unlink(&mut self, mut entry: TrashEntry) {
+        entry.trash_time = self.known.get(&entry.inode).copied();
+        if entry.trash_time == Some(0) {
+            self.removed_immediately += 1;
+            return;
+        }
+        let size = entry.size.unwrap_or(0);
+        let timestamp = entry.deleted;
+        self.in_trash.insert(entry.inode, self.entries.len());
+        self.entries.push(entry);
+        self.bytes += size;
+        self.record_bytes(timestamp);
+    }
+
+    /// Record a PURGE operation removing a file from trash
+    pub fn purge(&mut self, inode: u64, timestamp: NaiveDateTime) {
+        match self.leave(inode, timestamp) {
+            Some(entry) => entry.purged = Some(timestamp),
+            None => self.unknown_purges += 1,
+        }
+    }
+
+    /// Record an UNDEL operation restoring a file from trash. Returns the entry of the file, if
+    /// it was unlinked in the analyzed range.
+    pub fn undelete(&mut self, inode: u64, timestamp: NaiveDateTime) -> Option<&TrashEntry> {
+        if !self.in_trash.contains_key(&inode) {
+            self.unknown_undeletes += 1;
+            return None;
+        }
+        let entry = self.leave(inode, timestamp)?;
+        entry.undeleted = Some(timestamp);
+        Some(entry)
+    }
+
+    /// The files still in trash at the end of the analyzed range
+    pub fn still_in_trash(&self) -> impl Iterator<Item = &TrashEntry> {
+        self.entries.iter().filter(|e| e.in_trash())
+    }
+
+    /// Remove a file from trash and return its entry, if it was unlinked in the analyzed range
+    fn leave(&mut self, inode: u64, timestamp: NaiveDateTime) -> Option<&mut TrashEntry> {
+        let index = self.in_trash.remove(&inode)?;
+        let size = self.entries[index].size.unwrap_or(0);
+        self.bytes = self.bytes.saturating_sub(size);
+        self.record_bytes(timestamp);
+        Some(&mut self.entries[index])
+    }
+
+    /// Update the peak and the hourly amount of data in trash
+    fn record_bytes(&mut self, timestamp: NaiveDateTime) {
+        if self.bytes > self.peak_bytes {
+            self.peak_bytes = self.bytes;
+            self.peak_time = Some(timestamp);
+        }
+        let hour = timestamp
+            .duration_trunc(TimeDelta::hours(1))
+            .unwrap_or(timestamp);
+        let hourly = self.bytes_per_hour.entry(hour).or_insert(0);
+        *hourly = (*hourly).max(self.bytes);
+    }
+}
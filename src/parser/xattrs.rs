@@ -0,0 +1,110 @@
+use std::collections::HashMap;
+
+use chrono::NaiveDateTime;
+
+use super::line_parser::{Acl, SetXattr};
+
+/// Statistics of the SETXATTR operations of a single attribute name
+#[derive(Debug, Default)]
+pub struct XattrStats {
+    /// The number of operations setting the attribute
+    pub sets: u64,
+    /// The number of operations removing the attribute
+    pub removes: u64,
+    /// The sum of the lengths of all values set
+    pub value_bytes: u64,
+    /// The number of operations by inode
+    pub inodes: HashMap<u64, u64>,
+}
+
+impl XattrStats {
+    /// The total number of operations on the attribute
+    pub fn operations(&self) -> u64 {
+        self.sets + self.removes
+    }
+
+    /// The inode with the most operations on the attribute and its operation count
+    pub fn top_inode(&self) -> Option<(u64, u64)> {
+        self.inodes
+            .iter()
+            .max_by(|a, b| a.1.cmp(b.1).then(b.0.cmp(a.0)))
+            .map(|(inode, count)| (*inode, *count))
+    }
+}
+
+/// A change of the ACL of an inode
+#[derive(Debug)]
+pub struct AclChange {
+    /// The timestamp of the change
+    pub timestamp: NaiveDateTime,
+    /// The inode number
+    pub inode: u64,
+    /// The path of the inode at the time of the change
+    pub path: String,
+    /// The ACL type as written in the changelog
+    pub acl_type: String,
+    /// The new ACL, or None if it was deleted
+    pub acl: Option<String>,
+}
+
+/// Struct to track the SETXATTR operations by attribute name and inode, and every ACL change
+#[derive(Debug, Default)]
+pub struct Xattrs {
+    /// Statistics by attribute name
+    pub names: HashMap<String, XattrStats>,
+    /// The number of SETXATTR operations by inode
+    pub inodes: HashMap<u64, u64>,
+    /// All ACL changes, in the order they happened
+    pub acl_changes: Vec<AclChange>,
+}
+
+impl Xattrs {
+    /// Create a new Xattrs struct without any operations
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a SETXATTR operation
+    pub fn set(&mut self, set_xattr: SetXattr) {
+        let stats = self.names.entry(set_xattr.name).or_default();
+        if set_xattr.remove {
+            stats.removes += 1;
+        } else {
+            stats.sets += 1;
+            stats.value_bytes += set_xattr.value_length;
+        }
+        *stats.inodes.entry(set_xattr.inode).or_insert(0) += 1;
+        *self.inodes.entry(set_xattr.inode).or_insert(0) += 1;
+    }
+
+    /// Record a SETACL or DELETEACL operation
+    pub fn acl(&mut self, acl: Acl, path: String, timestamp: NaiveDateTime) {
+        self.acl_changes.push(AclChange {
+            timestamp,
+            inode: acl.inode,
+            path,
+            acl_type: acl.acl_type,
+            acl: acl.acl,
+        });
+    }
+
+    /// The attribute names with the most operations, at most `count`
+    pub fn top_names(&self, count: usize) -> Vec<(&str, &XattrStats)> {
+        let mut names: Vec<(&str, &XattrStats)> = self
+            .names
+            .iter()
+            .map(|(name, stats)| (name.as_str(), stats))
+            .collect();
+        names.sort_by(|a, b| b.1.operations().cmp(&a.1.operations()).then(a.0.cmp(b.0)));
+        names.truncate(count);
+        names
+    }
+
+    /// The inodes with the most SETXATTR operations, at most `count`
+    pub fn top_inodes(&self, count: usize) -> Vec<(u64, u64)> {
+        let mut inodes: Vec<(u64, u64)> = self.inodes.iter().map(|(i, c)| (*i, *c)).collect();
+        inodes.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        inodes.truncate(count);
+        inodes
+    }
+}
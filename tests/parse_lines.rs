@@ -1,4 +1,22 @@
 /// Integration test for parsing lines from a changelog file
+use chrono::TimeDelta;
+use saunafs_query::analysis::{
+    anomalies::{Anomalies, AnomalyKind},
+    deleted::{has_prefix, DeletedFiles},
+    extensions::Extensions,
+    goals::GoalImpact,
+    history::InodeHistory,
+    lifetime::Lifetimes,
+    locks::LockDurations,
+    open_files::OpenFiles,
+    owners::{parse_passwd, Owners},
+    size_histogram::SizeHistogram,
+    top::TopInodes,
+    who_touched::{glob_match, WhoTouched},
+};
+use saunafs_query::parser::activity::Activity;
+use saunafs_query::parser::node_types::NodeType;
+use saunafs_query::ChangelogResults;
 
 #[test]
 fn test_written_parse() {
@@ -8,13 +26,580 @@ fn test_written_parse() {
 
     assert_eq!(results.op_count.iter().map(|op| op.1).sum::<u64>(), 82);
     println!("{:?}", results.inodes);
-    assert_eq!(results.inodes.all.len(), 3);
+    // The three generations of the file and the root directory, created before the range
+    assert_eq!(results.inodes.all.len(), 4);
     assert_eq!(
         31923,
         results.inodes.all.iter().map(|i| i.written).sum::<u64>()
     )
 }
 
+#[test]
+fn test_truncated_parse() {
+    let test_str = include_str!("./file_changes.sfs").trim();
+
+    let (_, results) = test_utils::new_results(test_str);
+
+    assert_eq!(
+        25529,
+        results.inodes.all.iter().map(|i| i.truncated).sum::<u64>()
+    );
+    assert_eq!(
+        6394,
+        results
+            .inodes
+            .all
+            .iter()
+            .map(|i| i.net_size_change())
+            .sum::<i64>()
+    );
+    assert_eq!(
+        2,
+        results
+            .inodes
+            .all
+            .iter()
+            .map(|i| i.truncations)
+            .sum::<u64>()
+    );
+}
+
+#[test]
+fn test_untracked_inodes() {
+    let test_str = include_str!("./untracked.sfs").trim();
+    let (_, results) = test_utils::new_results(test_str);
+    // The length of inode 7 before the TRUNC is not known, so only the last 300 bytes count
+    let inode = results.inodes.find(7, 0).unwrap();
+    assert_eq!(inode.truncations, 1);
+    assert_eq!(inode.written, 300);
+    assert_eq!(inode.truncated, 0);
+    assert_eq!(inode.last_known_length, 800);
+
+    // Inode 9, created before the analyzed range, is ranked by its operations
+    let top = TopInodes::new(&results.inodes.all, 2);
+    let operations: Vec<(u64, u64)> = top
+        .by_operations
+        .iter()
+        .map(|i| (i.inode, i.operations))
+        .collect();
+    assert_eq!(operations, vec![(9, 5), (7, 3)]);
+}
+
+#[test]
+fn test_size_histogram() {
+    let test_str = include_str!("./file_changes.sfs").trim();
+    let (_, results) = test_utils::new_results(test_str);
+    let histogram = SizeHistogram::new(&results.inodes.all);
+    let counts: Vec<(u64, u64)> = histogram
+        .buckets
+        .iter()
+        .map(|b| (b.count, b.bytes))
+        .collect();
+    // Inodes 2 and 3 are deleted, only the final 4 bytes of inode 4 are counted
+    assert_eq!(&counts[..3], &[(0, 0), (1, 4), (0, 0)]);
+    assert_eq!(histogram.buckets.last().unwrap().label, "≥1T");
+
+    let test_str = include_str!("./files_dirs.sfs").trim();
+    let (_, results) = test_utils::new_results(test_str);
+    let histogram = SizeHistogram::new(&results.inodes.all);
+    assert_eq!(histogram.buckets[0].count, 5);
+    assert_eq!(histogram.buckets.iter().map(|b| b.count).sum::<u64>(), 5);
+}
+
+#[test]
+fn test_lifetimes() {
+    let test_str = include_str!("./temp_files.sfs").trim();
+    let (_, results) = test_utils::new_results(test_str);
+    let lifetimes = Lifetimes::new(&results.inodes.all);
+    let counts: Vec<u64> = lifetimes.buckets.iter().map(|b| b.count).collect();
+    assert_eq!(counts, vec![1, 1, 0, 1, 0, 0, 0]);
+    assert_eq!(lifetimes.short_lived, 2);
+    assert_eq!(lifetimes.short_lived_written, 1000);
+    assert_eq!(lifetimes.suffixes.len(), 1);
+    assert_eq!(lifetimes.suffixes[0].suffix, "tmp");
+    assert_eq!(lifetimes.suffixes[0].count, 2);
+}
+
+#[test]
+fn test_top_inodes() {
+    let test_str = include_str!("./file_changes.sfs").trim();
+    let (_, results) = test_utils::new_results(test_str);
+    let top = TopInodes::new(&results.inodes.all, 2);
+
+    let written: Vec<u64> = top.by_written.iter().map(|i| i.inode).collect();
+    assert_eq!(written, vec![3, 2]);
+    assert_eq!(top.by_rewrites.len(), 2);
+    assert!(top.by_rewrites.iter().all(|i| i.rewrites == 1));
+    assert_eq!(top.by_operations[0].inode, 3);
+    assert_eq!(top.by_operations[0].display_name(), "configuration.h");
+}
+
+#[test]
+fn test_inode_history() {
+    let test_str = include_str!("./inode_reuse.sfs").trim();
+    let (_, results) = test_utils::parse_results(
+        test_str,
+        ChangelogResults {
+            history: Some(InodeHistory::new(5)),
+            ..Default::default()
+        },
+    );
+    let history = results.history.unwrap();
+    let lengths: Vec<usize> = history
+        .generations
+        .iter()
+        .map(|g| g.entries.len())
+        .collect();
+    assert_eq!(lengths, vec![10, 5]);
+    assert_eq!(history.generations[1].generation, 2);
+
+    let state = &history.generations[0].entries.last().unwrap().state;
+    assert_eq!(state.name.as_deref(), Some("old.log.1"));
+    assert_eq!(state.size, Some(2048));
+    assert_eq!(state.uid, Some(1001));
+    assert_eq!(state.goal, Some(3));
+
+    let state = &history.generations[1].entries.last().unwrap().state;
+    assert_eq!(state.name.as_deref(), Some("new.txt"));
+    assert_eq!(state.size, Some(10));
+    assert_eq!(state.goal, None);
+}
+
+#[test]
+fn test_inode_generations() {
+    let test_str = include_str!("./inode_reuse.sfs").trim();
+    let (_, results) = test_utils::new_results(test_str);
+    let generations = results.inodes.generations(5);
+    assert_eq!(generations.len(), 2);
+    assert_eq!(generations[0].generation, 1);
+    assert_eq!(generations[0].written, 2048);
+    assert!(generations[0].deleted.is_some());
+    assert_eq!(generations[1].generation, 2);
+    assert_eq!(generations[1].written, 10);
+    assert!(generations[1].deleted.is_none());
+
+    // The deletion of the first generation is not seen, it must not be mixed with the second
+    let test_str = "1: 1710500000|CREATE(1,a,f,420,0,0,0):7
+2: 1710500001|LENGTH(7,5)
+3: 1710500002|CREATE(1,b,f,420,0,0,0):7
+4: 1710500003|LENGTH(7,3)";
+    let (_, results) = test_utils::new_results(test_str);
+    let generations = results.inodes.generations(7);
+    let written: Vec<(u64, u64)> = generations
+        .iter()
+        .map(|i| (i.generation, i.written))
+        .collect();
+    assert_eq!(written, vec![(1, 5), (2, 3)]);
+}
+
+#[test]
+fn test_chunks() {
+    let test_str = include_str!("./file_changes.sfs").trim();
+    let (_, results) = test_utils::new_results(test_str);
+    let chunks = &results.chunks;
+    assert_eq!(chunks.all.len(), 6);
+    assert_eq!(chunks.new_chunks, 6);
+    assert_eq!(chunks.reused_chunks, 3);
+    assert_eq!(chunks.next_chunk_id_advance(), 10);
+
+    let chunk = &chunks.all[&15];
+    assert_eq!((chunk.inode, chunk.index), (3, 0));
+    assert_eq!((chunk.writes, chunk.truncations, chunk.unlocks), (3, 1, 4));
+    assert_eq!((chunks.all[&17].inode, chunks.all[&17].index), (4, 0));
+
+    // Only an opflag of 1 allocates a new chunk
+    let test_str = "1: 1710600000|WRITE(2,0,2,1029384756):40
+2: 1710600000|WRITE(2,1,1,1029384756):41";
+    let (_, results) = test_utils::new_results(test_str);
+    assert_eq!(results.chunks.new_chunks, 1);
+    assert_eq!(results.chunks.reused_chunks, 1);
+}
+
+#[test]
+fn test_lock_durations() {
+    let test_str = "1: 1710600000|WRITE(2,0,1,1029384756):40
+2: 1710600005|UNLOCK(40)
+3: 1710600010|WRITE(3,0,1,2938475610):41
+4: 1710600011|UNLOCK(41)
+5: 1710600020|WRITE(2,1,1,3847561029):42
+6: 1710600050|UNLOCK(42)
+7: 1710600060|WRITE(4,0,1,4756102938):43";
+    let (_, results) = test_utils::new_results(test_str);
+    let locks = LockDurations::new(&results.chunks, 10);
+    assert_eq!(locks.count, 3);
+    assert_eq!(locks.percentiles[0], (50, TimeDelta::seconds(5)));
+    assert_eq!(locks.max, TimeDelta::seconds(30));
+    assert_eq!(locks.longest[0].chunk_id, 42);
+    assert_eq!(locks.inodes[0].inode, 2);
+    assert_eq!(locks.inodes[0].total, TimeDelta::seconds(35));
+    let never_unlocked: Vec<u64> = locks.never_unlocked.iter().map(|c| c.chunk_id).collect();
+    assert_eq!(never_unlocked, vec![43]);
+}
+
+#[test]
+fn test_open_files() {
+    let test_str = include_str!("./file_changes.sfs").trim();
+    let (_, results) = test_utils::new_results(test_str);
+    let open_files = OpenFiles::new(&results.sessions, 10);
+    assert_eq!(open_files.count, 11);
+    assert_eq!(open_files.max, TimeDelta::seconds(26));
+    assert!(open_files.still_open.is_empty());
+    assert_eq!(results.sessions.peak_open, 1);
+
+    let test_str = "1: 1710700000|ACQUIRE(2,1)
+2: 1710700001|ACQUIRE(3,1)
+3: 1710700002|ACQUIRE(3,2)
+4: 1710700010|RELEASE(2,1)
+5: 1710700011|RELEASE(4,2)";
+    let (_, results) = test_utils::new_results(test_str);
+    let open_files = OpenFiles::new(&results.sessions, 10);
+    assert_eq!(results.sessions.peak_open, 3);
+    assert_eq!(open_files.count, 1);
+    assert_eq!(open_files.still_open.len(), 2);
+    let sessions: Vec<(u64, usize)> = open_files
+        .sessions
+        .iter()
+        .map(|s| (s.session, s.count))
+        .collect();
+    assert_eq!(sessions, vec![(1, 1), (2, 1)]);
+}
+
+#[test]
+fn test_session_activity() {
+    let test_str = include_str!("./file_changes.sfs").trim();
+    let (_, results) = test_utils::new_results(test_str);
+    let session = &results.sessions.all[&1];
+    assert!(session.created.is_some());
+    assert_eq!(session.files_opened, 11);
+    assert_eq!(session.written_inodes.len(), 3);
+    assert_eq!(session.written, 31923);
+    assert_eq!(results.sessions.unattributed_written, 0);
+
+    // Data written to an inode is attributed to the session that opened it most recently
+    let test_str = "1: 1710700000|SESSION():1
+2: 1710700000|SESSION():2
+3: 1710700001|CREATE(1,a,f,420,0,0,0):5
+4: 1710700002|ACQUIRE(5,1)
+5: 1710700003|ACQUIRE(5,2)
+6: 1710700004|WRITE(5,0,1,1234567890):7
+7: 1710700004|LENGTH(5,100)
+8: 1710700005|RELEASE(5,2)
+9: 1710700006|LENGTH(5,150)";
+    let (_, results) = test_utils::new_results(test_str);
+    assert_eq!(results.sessions.all[&1].written, 50);
+    assert_eq!(results.sessions.all[&2].written, 100);
+    assert_eq!(results.sessions.all[&2].written_inodes.len(), 1);
+    assert!(results.sessions.all[&1].written_inodes.is_empty());
+}
+
+#[test]
+fn test_owners() {
+    let test_str = include_str!("./inode_reuse.sfs").trim();
+    let (_, results) = test_utils::new_results(test_str);
+    let owners = Owners::new(&results.inodes.all);
+    let users: Vec<(u32, u64, u64, u64)> = owners
+        .users
+        .iter()
+        .map(|o| (o.id, o.files_created, o.deleted, o.written))
+        .collect();
+    // The first file was chowned to 1001 before being deleted
+    assert_eq!(users, vec![(1001, 1, 1, 2048), (1000, 1, 0, 10)]);
+    // The root directory, accessed once, was created before the analyzed range
+    assert_eq!(owners.unknown_owner, 1);
+
+    let test_str = include_str!("./files_dirs.sfs").trim();
+    let (_, results) = test_utils::new_results(test_str);
+    let owners = Owners::new(&results.inodes.all);
+    assert_eq!(owners.groups.len(), 1);
+    assert_eq!(owners.groups[0].files_created, 5);
+    assert_eq!(owners.groups[0].dirs_created, 6);
+
+    let users = parse_passwd("# comment\nroot:x:0:0:root:/root:/bin/bash\nbroken\n");
+    assert_eq!(users.len(), 1);
+    assert_eq!(users[&0], "root");
+}
+
+#[test]
+fn test_attribute_changes() {
+    let test_str = include_str!("./permissions.sfs").trim();
+    let (_, results) = test_utils::new_results(test_str);
+    let changes = &results.attribute_changes.changes;
+    let summary: Vec<(&str, bool, bool, Vec<&str>)> = changes
+        .iter()
+        .map(|c| (c.path.as_str(), c.is_chmod(), c.is_chown(), c.warnings()))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            ("/srv/run.sh", true, false, vec![]),
+            (
+                "/srv/run.sh",
+                true,
+                true,
+                vec!["setuid", "owner root", "group root"]
+            ),
+            ("/srv/start.sh", true, false, vec!["world-writable"]),
+            // The first ATTR of inodes created before the analyzed range has unknown old
+            // attributes, so only the new ones are checked
+            ("<inode 40>", true, true, vec![]),
+            ("<inode 40>", true, false, vec!["world-writable"]),
+            (
+                "<inode 41>",
+                true,
+                true,
+                vec!["setuid", "setgid", "owner root", "group root"]
+            ),
+        ]
+    );
+    assert!(changes[3].old.is_none());
+}
+
+#[test]
+fn test_goals() {
+    let test_str = include_str!("./goals.sfs").trim();
+    let (_, results) = test_utils::new_results(test_str);
+    let goals = &results.goals;
+    let applied: Vec<(u32, u64, u64)> = goals
+        .applied
+        .iter()
+        .map(|(goal, stats)| (*goal, stats.operations, stats.changed))
+        .collect();
+    assert_eq!(applied, vec![(1, 1, 1), (2, 2, 2), (3, 2, 1), (4, 1, 12)]);
+    // The recursive change of /data also sets the goal of the file below it
+    assert_eq!(goals.get(3), Some(4));
+    assert_eq!(goals.changes.len(), 5);
+    assert_eq!(goals.recursive.len(), 1);
+    assert_eq!(goals.recursive[0].changed, 12);
+    assert_eq!(goals.recursive[0].descendants, 1);
+
+    // 2 -> 3 adds a copy, 3 -> 1 removes two, the recursive 1 -> 4 adds three, the first change
+    // and inode 60 are unknown
+    let impact = GoalImpact::new(goals, None);
+    assert_eq!(impact.replicated_bytes, 4000000);
+    assert_eq!(impact.removed_bytes, 2000000);
+    assert_eq!(impact.unknown, 2);
+
+    let impact = GoalImpact::new(goals, Some(1));
+    assert_eq!(impact.replicated_bytes, 5000000);
+    assert_eq!(impact.unknown, 1);
+}
+
+#[test]
+fn test_recursive_goals() {
+    let test_str = include_str!("./goals_recursive.sfs").trim();
+    let (_, results) = test_utils::new_results(test_str);
+    let goals = &results.goals;
+    assert_eq!(goals.get(3), Some(3));
+    assert_eq!(goals.get(5), Some(2));
+    assert_eq!(results.inodes.find(5, 1).unwrap().goal, Some(2));
+    assert_eq!(goals.recursive[0].descendants, 3);
+    assert_eq!(goals.recursive[1].descendants, 0);
+    let changes: Vec<(u64, Option<u32>, u32)> = goals
+        .changes
+        .iter()
+        .map(|c| (c.inode, c.old, c.new))
+        .collect();
+    assert_eq!(
+        changes,
+        vec![
+            (4, None, 2),
+            (4, Some(2), 3),
+            (5, None, 3),
+            (5, Some(3), 1),
+            (4, Some(3), 15),
+            (5, Some(1), 2)
+        ]
+    );
+
+    // 2 -> 3 of a.bin adds a copy, 3 -> 1 of b.bin removes two, the goal 15 is not a copy goal,
+    // the recursive 1 -> 2 of the file b.bin adds a copy
+    let impact = GoalImpact::new(goals, None);
+    assert_eq!(impact.replicated_bytes, 1500000);
+    assert_eq!(impact.removed_bytes, 1000000);
+    assert_eq!(impact.unknown, 2);
+    assert_eq!(impact.not_estimated, 1);
+
+    let impact = GoalImpact::new(goals, Some(1));
+    assert_eq!(impact.replicated_bytes, 3500000);
+    assert_eq!(impact.unknown, 0);
+}
+
+#[test]
+fn test_trash() {
+    let test_str = include_str!("./trash.sfs").trim();
+    let (_, results) = test_utils::new_results(test_str);
+    let trash = &results.trash;
+    let paths: Vec<&str> = trash.entries.iter().map(|e| e.path.as_str()).collect();
+    assert_eq!(
+        paths,
+        vec!["/projects/a.dat", "/projects/b.dat", "/old.dat"]
+    );
+    // tmp.dat and keep.dat, whose trash time was set to 0 recursively, skip the trash
+    assert_eq!(trash.removed_immediately, 2);
+    assert_eq!(trash.entries[0].trash_time, Some(3600));
+    assert!(trash.entries[0].purged.is_some());
+    assert!(trash.entries[1].undeleted.is_some());
+    assert_eq!(trash.entries[1].uid, Some(1001));
+    assert_eq!(trash.entries[2].size, None);
+    assert!(trash.entries[1].known_file);
+    assert!(!trash.entries[2].known_file);
+    assert_eq!(trash.unknown_purges, 1);
+    assert_eq!(trash.peak_bytes, 8000);
+    assert_eq!(trash.bytes, 0);
+    let in_trash: Vec<u64> = trash.still_in_trash().map(|e| e.inode).collect();
+    assert_eq!(in_trash, vec![40]);
+}
+
+#[test]
+fn test_undelete() {
+    let after_undelete = [
+        "24: 1711004100|LENGTH(4,4000)",
+        "25: 1711004110|MOVE(2,b.dat,1,b.dat):4",
+        "26: 1711004120|UNLINK(1,b.dat):4",
+    ];
+    let test_str =
+        include_str!("./trash.sfs").trim().to_string() + "\n" + &after_undelete.join("\n");
+    let (_, results) = test_utils::new_results(&test_str);
+
+    // The restored b.dat is the same inode again, with its name and length
+    let entry = results.trash.entries.last().unwrap();
+    assert_eq!((entry.inode, entry.path.as_str()), (4, "/b.dat"));
+    assert_eq!(entry.size, Some(4000));
+    assert_eq!(results.inodes.generations(4).len(), 1);
+    let inode = results.inodes.find(4, 1).unwrap();
+    assert_eq!(inode.written, 4000);
+    assert_eq!(inode.operations, 7);
+    assert!(inode.deleted.is_some());
+}
+
+#[test]
+fn test_deleted_files() {
+    let restorable = [
+        "24: 1711004050|UNLINK(2,c.dat):7",
+        "25: 1711004060|UNLINK(1,projects2):8",
+        "26: 1711004070|UNLINK(2,d.dat):9",
+    ];
+    let test_str = include_str!("./trash.sfs").trim().to_string() + "\n" + &restorable.join("\n");
+    let (_, results) = test_utils::new_results(&test_str);
+
+    let deleted = DeletedFiles::new(&results.trash, Some("/projects/"), None);
+    let paths: Vec<&str> = deleted.files.iter().map(|e| e.path.as_str()).collect();
+    assert_eq!(paths, vec!["/projects/c.dat", "/projects/d.dat"]);
+    assert_eq!(deleted.unknown_size, 2);
+    assert_eq!(deleted.unknown_type, 2);
+
+    let deleted = DeletedFiles::new(&results.trash, None, Some(1000));
+    assert!(deleted.files.is_empty());
+    assert!(!has_prefix("/projects2", "/projects"));
+    assert!(has_prefix("/projects", "/projects"));
+}
+
+#[test]
+fn test_who_touched() {
+    let test_str = include_str!("./renames.sfs").trim();
+    let mut results = ChangelogResults {
+        who_touched: Some(WhoTouched::new("report.csv".to_string())),
+        ..Default::default()
+    };
+    results = test_utils::parse_results(test_str, results).1;
+    let who_touched = results.who_touched.unwrap();
+    let events: Vec<(&str, u64, Option<u64>)> = who_touched
+        .events
+        .iter()
+        .map(|e| (e.operation.as_str(), e.inode, e.session))
+        .collect();
+    assert_eq!(
+        events,
+        vec![
+            ("CREATE", 4, Some(7)),
+            ("MOVE", 3, Some(7)),
+            ("REPLACE", 4, Some(7)),
+            ("ATTR", 3, None),
+            ("MOVE", 3, None),
+            ("ATTR", 3, None),
+            ("UNLINK", 3, None),
+        ]
+    );
+    assert_eq!(who_touched.events[2].path, "/data/report.csv");
+    assert_eq!(who_touched.events[4].path, "/data/report.csv -> /final.csv");
+
+    assert!(glob_match("/data/*.csv", "/data/report.csv"));
+    assert!(glob_match("rep?rt*", "report.csv.part"));
+    assert!(!glob_match("*.csv", "report.csv.part"));
+}
+
+#[test]
+fn test_renames() {
+    let test_str = include_str!("./renames.sfs").trim();
+    let (_, results) = test_utils::new_results(test_str);
+    let renames = &results.renames;
+    assert_eq!(renames.moves, 2);
+    assert_eq!(renames.cross_directory, 1);
+    assert_eq!(renames.replacing, 1);
+    assert_eq!(renames.atomic_saves.len(), 1);
+    assert_eq!(
+        renames.atomic_saves[0].temporary_path,
+        "/data/report.csv.part"
+    );
+    assert_eq!(renames.atomic_saves[0].replaced, 4);
+    assert_eq!(
+        renames.lineage(3).unwrap(),
+        ["/data/report.csv.part", "/data/report.csv", "/final.csv"]
+    );
+
+    // The replaced file is deleted and moved to trash
+    assert!(results.inodes.find(4, 1).unwrap().deleted.is_some());
+    let trashed: Vec<u64> = results.trash.entries.iter().map(|e| e.inode).collect();
+    assert_eq!(trashed, vec![4, 3]);
+}
+
+#[test]
+fn test_snapshots() {
+    let test_str = include_str!("./snapshots.sfs").trim();
+    let (_, results) = test_utils::new_results(test_str);
+    let events = &results.snapshots.events;
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[0].source_path, "/home/alice");
+    assert_eq!(events[0].path, "/snapshots/alice-1");
+    assert_eq!((events[0].inodes, events[0].bytes), (4, 3000));
+    assert_eq!((events[1].inodes, events[1].bytes), (3, 1000));
+    assert!(events[1].can_overwrite);
+    assert_eq!(results.snapshots.inodes(), 7);
+}
+
+#[test]
+fn test_links() {
+    let test_str = include_str!("./links.sfs").trim();
+    let lines: Vec<&str> = test_str.split('\n').collect();
+
+    // Unlinking one of two names keeps the inode
+    let (_, results) = test_utils::new_results(&lines[..7].join("\n"));
+    let file = results.inodes.find(3, 1).unwrap();
+    assert!(file.deleted.is_none());
+    assert_eq!(results.inodes.links(3), Some(1));
+    assert_eq!(results.namespace.path(3), "/shared/renamed.bin");
+    assert_eq!(results.namespace.lookup(2, "data.bin"), None);
+    assert!(results.trash.entries.is_empty());
+    let symlink = results.inodes.find(4, 1).unwrap();
+    assert_eq!(symlink.node_type, Some('l'));
+    assert_eq!(symlink.symlink_target.as_deref(), Some("shared/data.bin"));
+
+    // An inode with an unknown link count keeps one name after LINK and UNLINK
+    let (_, results) = test_utils::new_results(test_str);
+    assert!(results.inodes.find(3, 1).unwrap().deleted.is_some());
+    let deleted: Vec<&str> = results
+        .trash
+        .entries
+        .iter()
+        .map(|e| e.path.as_str())
+        .collect();
+    assert_eq!(deleted, vec!["/shared/renamed.bin"]);
+    assert_eq!(results.inode_created_count, 3);
+    // Inode 9 is not moved to trash by unlinking its second name
+    assert_eq!(results.inodes.links(9), Some(1));
+    assert_eq!(results.namespace.lookup(2, "old-link"), None);
+}
+
 #[cfg(test)]
 pub mod test_utils {
     use saunafs_query::parse_line;
@@ -22,11 +607,17 @@ pub mod test_utils {
     use saunafs_query::TimestampRange;
 
     pub fn new_results(test_str: &str) -> (TimestampRange, ChangelogResults) {
+        parse_results(test_str, ChangelogResults::default())
+    }
+
+    pub fn parse_results(
+        test_str: &str,
+        mut results: ChangelogResults,
+    ) -> (TimestampRange, ChangelogResults) {
         let mut timestamp = TimestampRange {
             end: chrono::offset::Local::now().naive_utc(),
             ..Default::default()
         };
-        let mut results = ChangelogResults::default();
         test_str.split('\n').for_each(|line| {
             parse_line(line, &mut results, &mut timestamp).unwrap();
         });
@@ -43,3 +634,155 @@ fn test_directory_file_inode_counts() {
     assert_eq!(results.file_count, 5);
     assert_eq!(results.dir_count, 6);
 }
+
+#[test]
+fn test_node_types() {
+    let test_str = include_str!("./node_types.sfs").trim();
+    let (_, results) = test_utils::new_results(test_str);
+    let node_types = &results.node_types;
+    for node_type in [
+        NodeType::File,
+        NodeType::Directory,
+        NodeType::Symlink,
+        NodeType::Fifo,
+        NodeType::BlockDevice,
+        NodeType::CharDevice,
+        NodeType::Socket,
+        NodeType::Unknown('x'),
+    ] {
+        assert_eq!(node_types.count(node_type), 1, "{}", node_type.label());
+    }
+    assert_eq!(node_types.unknown(), 1);
+    assert_eq!((results.file_count, results.dir_count), (1, 1));
+    assert_eq!(results.inode_created_count, 8);
+}
+
+#[test]
+fn test_xattrs() {
+    let test_str = include_str!("./xattrs.sfs").trim();
+    let (_, results) = test_utils::new_results(test_str);
+    let xattrs = &results.xattrs;
+    let top = xattrs.top_names(1);
+    assert_eq!(top[0].0, "user.checksum");
+    let stats = top[0].1;
+    assert_eq!((stats.sets, stats.removes, stats.value_bytes), (3, 1, 14));
+    assert_eq!(stats.top_inode(), Some((3, 3)));
+    assert_eq!(xattrs.names["security.selinux"].value_bytes, 17);
+    assert_eq!(xattrs.top_inodes(1), vec![(3, 4)]);
+
+    let acls = &xattrs.acl_changes;
+    assert_eq!(acls.len(), 2);
+    assert_eq!(acls[0].path, "/share");
+    assert_eq!(acls[0].acl.as_deref(), Some("u::rwx,g::r-x,o::---"));
+    assert_eq!(acls[1].acl, None);
+}
+
+#[test]
+fn test_quotas() {
+    let test_str = include_str!("./quotas.sfs").trim();
+    let (_, results) = test_utils::new_results(test_str);
+    let changes = &results.quotas.changes;
+    assert_eq!(changes.len(), 4);
+    assert!(changes[2].quota.group && changes[2].quota.inodes);
+    assert_eq!(changes[3].old, Some(2147483648));
+    assert_eq!(changes[3].quota.limit, 0);
+    // The removed hard limit falls back to the soft limit
+    assert_eq!(results.quotas.size_limit(false, 1000), Some(1073741824));
+
+    let owners = Owners::new(&results.inodes.all);
+    let user = owners.users.iter().find(|u| u.id == 1000).unwrap();
+    assert_eq!((user.inode_growth, user.byte_growth), (1, 3000));
+    let user = owners.users.iter().find(|u| u.id == 1001).unwrap();
+    assert_eq!((user.inode_growth, user.byte_growth), (0, 500));
+}
+
+#[test]
+fn test_hot_directories() {
+    let test_str = include_str!("./renames.sfs").trim();
+    let (_, results) = test_utils::new_results(test_str);
+    let directories = &results.directories;
+    let by_operations: Vec<(u64, u64)> = directories
+        .by_operations(2)
+        .iter()
+        .map(|d| (d.inode, d.operations))
+        .collect();
+    assert_eq!(by_operations, vec![(2, 5), (1, 3)]);
+
+    let data = &directories.all[&2];
+    assert_eq!((data.added, data.removed), (3, 2));
+    assert_eq!(data.churn(), 2);
+    let by_churn: Vec<u64> = directories.by_churn(10).iter().map(|d| d.inode).collect();
+    assert_eq!(by_churn, vec![2, 1]);
+}
+
+#[test]
+fn test_extensions() {
+    let test_str = include_str!("./temp_files.sfs").trim();
+    let (_, results) = test_utils::new_results(test_str);
+    let extensions = Extensions::new(&results.inodes.all).extensions;
+    let stats: Vec<(&str, u64, u64, u64, u64)> = extensions
+        .iter()
+        .map(|e| {
+            (
+                e.extension.as_str(),
+                e.created,
+                e.deleted,
+                e.written,
+                e.average_size(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        stats,
+        vec![("tmp", 2, 2, 1000, 500), ("o", 1, 1, 5000, 5000)]
+    );
+}
+
+#[test]
+fn test_anomalies() {
+    let test_str = include_str!("./anomalies.sfs").trim();
+    let results = ChangelogResults {
+        activity: Some(Activity::new()),
+        ..Default::default()
+    };
+    let (timeline, results) = test_utils::parse_results(test_str, results);
+    let activity = results.activity.as_ref().unwrap();
+
+    let anomalies = Anomalies::new(activity, &results.namespace, &timeline, 60, 5);
+    let found: Vec<(AnomalyKind, usize, &str)> = anomalies
+        .anomalies
+        .iter()
+        .map(|a| (a.kind.clone(), a.count, a.subtree.as_str()))
+        .collect();
+    assert_eq!(
+        found,
+        vec![
+            (AnomalyKind::MassRewrite, 6, "?"),
+            (AnomalyKind::MassRename("locked".to_string()), 6, "/docs"),
+            (AnomalyKind::MassDeletion, 6, "/docs"),
+        ]
+    );
+    assert_eq!(anomalies.anomalies[2].inodes, vec![10, 11, 12, 13, 14, 15]);
+
+    let anomalies = Anomalies::new(activity, &results.namespace, &timeline, 60, 7);
+    assert!(anomalies.anomalies.is_empty());
+
+    // A single file rewritten over and over in one window is a single affected inode
+    let test_str: Vec<String> = (0..10)
+        .flat_map(|i| {
+            [
+                format!("{}: 1711700000|LENGTH(10,0)", 2 * i + 1),
+                format!("{}: 1711700000|LENGTH(10,500)", 2 * i + 2),
+            ]
+        })
+        .collect();
+    let results = ChangelogResults {
+        activity: Some(Activity::new()),
+        ..Default::default()
+    };
+    let (timeline, results) = test_utils::parse_results(&test_str.join("\n"), results);
+    let activity = results.activity.as_ref().unwrap();
+    assert_eq!(activity.rewrites.len(), 10);
+    let anomalies = Anomalies::new(activity, &results.namespace, &timeline, 60, 5);
+    assert!(anomalies.anomalies.is_empty());
+}